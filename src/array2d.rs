@@ -1,112 +1,283 @@
+#![cfg_attr(not(test), allow(dead_code))]
+
 use core::fmt::Debug;
-use std::slice::Iter;
-use std::slice::IterMut;
+use std::iter::StepBy;
+use std::ops::Range;
+use std::slice::{ChunksExact, ChunksExactMut, Iter, IterMut};
 
-use crate::vec2::Vec2i32;
+use crate::{
+    arrayn::{all_offsets, orthogonal_offsets, ArrayIndex, ArrayN},
+    parse::{self, ParseError},
+    rect::Rect,
+    vec2::{vec2, Vec2, Vec2i32},
+};
 
-#[derive(Clone)]
-pub struct Array2D<T: Default + Clone> {
-    width: usize,
-    height: usize,
-    data: Vec<T>,
-}
+/// A 2D array, stored row by row.
+pub type Array2D<T> = ArrayN<T, 2>;
 
 impl<T: Default + Clone> Array2D<T> {
     pub fn new(width: usize, height: usize) -> Self {
-        let data = vec![T::default(); width * height];
-        Self {
-            width,
-            height,
-            data,
-        }
+        Self::from_shape([width, height])
+    }
+
+    /// An array with one cell per point of `rect`. Point `p` is stored at `p - rect.min`.
+    pub fn new_covering(rect: &Rect<i32>) -> Self {
+        let size = rect.size().try_cast::<usize>().unwrap();
+        Self::new(size.x, size.y)
     }
 
     pub fn width(&self) -> usize {
-        self.width
+        self.shape[0]
     }
 
     pub fn height(&self) -> usize {
-        self.height
+        self.shape[1]
     }
 
     pub fn slice(&self, y: usize) -> &[T] {
-        let start = y * self.width;
-        let end = (y + 1) * self.width;
+        let start = y * self.width();
+        let end = (y + 1) * self.width();
         &self.data[start..end]
     }
 
     pub fn slice_mut(&mut self, y: usize) -> &mut [T] {
-        let start = y * self.width;
-        let end = (y + 1) * self.width;
+        let start = y * self.width();
+        let end = (y + 1) * self.width();
         &mut self.data[start..end]
     }
 
-    pub fn fill(&mut self, value: T) {
-        for v in &mut self.data {
-            *v = value.clone();
+    /// Iterates over the rows, from top to bottom. An array without columns has no rows.
+    pub fn rows(&self) -> ChunksExact<'_, T> {
+        // The data is empty without columns, so any chunk size yields nothing.
+        self.data.chunks_exact(self.width().max(1))
+    }
+
+    pub fn rows_mut(&mut self) -> ChunksExactMut<'_, T> {
+        let width = self.width().max(1);
+        self.data.chunks_exact_mut(width)
+    }
+
+    /// Iterates over the cells of column `x`, from top to bottom.
+    pub fn column(&self, x: usize) -> StepBy<Iter<'_, T>> {
+        assert!(x < self.width(), "column {} out of bounds", x);
+        self.data[x..].iter().step_by(self.width())
+    }
+
+    pub fn column_mut(&mut self, x: usize) -> StepBy<IterMut<'_, T>> {
+        assert!(x < self.width(), "column {} out of bounds", x);
+        let width = self.width();
+        self.data[x..].iter_mut().step_by(width)
+    }
+
+    /// Iterates over the columns, from left to right.
+    pub fn columns(&self) -> impl Iterator<Item = StepBy<Iter<'_, T>>> {
+        (0..self.width()).map(|x| self.column(x))
+    }
+
+    /// Copies the rows in `range` into a new array.
+    pub fn crop_rows(&self, range: Range<usize>) -> Self {
+        let data = self.data[range.start * self.width()..range.end * self.width()].to_vec();
+        Self {
+            shape: [self.width(), range.len()],
+            data,
         }
     }
 
-    pub fn iter(&self) -> Iter<'_, T> {
-        self.data.iter()
+    /// Builds an array of the given size, taking the value at each position from `f`.
+    fn from_fn(width: usize, height: usize, f: impl Fn(usize, usize) -> T) -> Self {
+        let mut data = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                data.push(f(x, y));
+            }
+        }
+        Self {
+            shape: [width, height],
+            data,
+        }
     }
 
-    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
-        self.data.iter_mut()
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height(), self.width(), |x, y| self.at((y, x)).clone())
     }
-}
 
-pub trait Array2DIndex {
-    fn coords(&self) -> (usize, usize);
+    /// Rotates by 90 degrees clockwise, so the top row becomes the right column.
+    pub fn rotate90(&self) -> Self {
+        let h = self.height();
+        Self::from_fn(h, self.width(), |x, y| self.at((y, h - 1 - x)).clone())
+    }
+
+    pub fn rotate180(&self) -> Self {
+        let mut data = self.data.clone();
+        data.reverse();
+        Self { data, ..*self }
+    }
+
+    /// Rotates by 90 degrees counter-clockwise, so the top row becomes the left column.
+    pub fn rotate270(&self) -> Self {
+        let w = self.width();
+        Self::from_fn(self.height(), w, |x, y| self.at((w - 1 - y, x)).clone())
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_x(&self) -> Self {
+        let mut ret = self.clone();
+        for row in ret.rows_mut() {
+            row.reverse();
+        }
+        ret
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_y(&self) -> Self {
+        let data = self.rows().rev().flatten().cloned().collect();
+        Self { data, ..*self }
+    }
 }
 
-impl Array2DIndex for (usize, usize) {
-    fn coords(&self) -> (usize, usize) {
-        *self
+impl ArrayIndex<2> for (usize, usize) {
+    fn position(&self) -> [i64; 2] {
+        [self.0 as i64, self.1 as i64]
+    }
+
+    fn coords(&self) -> [usize; 2] {
+        [self.0, self.1]
     }
 }
 
-impl Array2DIndex for (i32, i32) {
-    fn coords(&self) -> (usize, usize) {
-        (self.0 as usize, self.1 as usize)
+impl ArrayIndex<2> for (i32, i32) {
+    fn position(&self) -> [i64; 2] {
+        [self.0 as i64, self.1 as i64]
     }
 }
 
-impl Array2DIndex for Vec2i32 {
-    fn coords(&self) -> (usize, usize) {
-        (self.x as usize, self.y as usize)
+impl ArrayIndex<2> for Vec2i32 {
+    fn position(&self) -> [i64; 2] {
+        self.cast::<i64>().into()
     }
 }
 
 impl<T: Default + Clone> Array2D<T> {
-    pub fn at<P: Array2DIndex>(&self, pos: P) -> &T {
-        let coords = pos.coords();
-        debug_assert!(coords.0 < self.width);
-        debug_assert!(coords.1 < self.height);
-        &self.data[coords.0 + self.width * coords.1]
+    /// Wraps `pos` around the edges of the array, as if it were a torus. Panics if the array is
+    /// empty, since nothing can wrap onto it.
+    pub fn wrap<P: ArrayIndex<2>>(&self, pos: P) -> Vec2i32 {
+        assert!(
+            self.width() > 0 && self.height() > 0,
+            "can't wrap a position around an empty {}x{} array",
+            self.width(),
+            self.height()
+        );
+        let size = vec2(self.width() as i64, self.height() as i64);
+        Vec2::from(pos.position())
+            .rem_euclid(size)
+            .map(|c| c as i32)
+    }
+
+    pub fn at_wrapping<P: ArrayIndex<2>>(&self, pos: P) -> &T {
+        self.at(self.wrap(pos))
+    }
+
+    pub fn at_wrapping_mut<P: ArrayIndex<2>>(&mut self, pos: P) -> &mut T {
+        self.at_mut(self.wrap(pos))
+    }
+
+    /// Iterates over the positions left, right, above and below `pos` that lie in the array.
+    pub fn neighbors4<P: ArrayIndex<2>>(&self, pos: P) -> impl Iterator<Item = Vec2i32> + '_ {
+        self.neighbors_orthogonal(pos).map(|[x, y]| vec2(x, y))
+    }
+
+    /// Iterates over the positions around `pos`, including diagonals, that lie in the array.
+    pub fn neighbors8<P: ArrayIndex<2>>(&self, pos: P) -> impl Iterator<Item = Vec2i32> + '_ {
+        self.neighbors(pos).map(|[x, y]| vec2(x, y))
+    }
+
+    /// Iterates over the 4 neighbours of `pos`, wrapping around the edges of the array.
+    pub fn neighbors4_wrapping<P: ArrayIndex<2>>(
+        &self,
+        pos: P,
+    ) -> impl Iterator<Item = Vec2i32> + '_ {
+        let [x, y] = Self::signed(pos);
+        orthogonal_offsets().map(move |[dx, dy]| self.wrap((x + dx, y + dy)))
     }
 
-    pub fn at_mut<P: Array2DIndex>(&mut self, pos: P) -> &mut T {
-        let coords = pos.coords();
-        debug_assert!(coords.0 < self.width);
-        debug_assert!(coords.1 < self.height);
-        &mut self.data[coords.0 + self.width * coords.1]
+    /// Iterates over the 8 neighbours of `pos`, wrapping around the edges of the array.
+    pub fn neighbors8_wrapping<P: ArrayIndex<2>>(
+        &self,
+        pos: P,
+    ) -> impl Iterator<Item = Vec2i32> + '_ {
+        let [x, y] = Self::signed(pos);
+        all_offsets().map(move |[dx, dy]| self.wrap((x + dx, y + dy)))
+    }
+}
+
+/// Positions of marker characters found while parsing a grid from text.
+#[derive(Clone, Debug, Default)]
+pub struct Markers {
+    positions: Vec<(char, Vec2i32)>,
+}
+
+impl Markers {
+    /// Returns the position of the first occurrence of `ch`.
+    pub fn get(&self, ch: char) -> Option<Vec2i32> {
+        self.all(ch).next()
     }
 
-    pub fn set<P: Array2DIndex>(&mut self, pos: P, value: T) {
-        *self.at_mut(pos) = value;
+    /// Iterates over the positions of all occurrences of `ch`, row by row.
+    pub fn all(&self, ch: char) -> impl Iterator<Item = Vec2i32> + '_ {
+        self.positions
+            .iter()
+            .filter(move |(c, _)| *c == ch)
+            .map(|(_, pos)| *pos)
+    }
+}
+
+impl<T: Default + Clone> Array2D<T> {
+    /// Parses the rows of `s`, a slice of `input`, into a grid, mapping each character with `f`.
+    /// The grid is as wide as the longest row, shorter rows are padded with the default value.
+    /// Blank lines at the end are ignored, blank lines between rows are an error. The positions
+    /// of the characters in `markers` are collected along the way.
+    pub fn from_str_map(
+        input: &str,
+        s: &str,
+        markers: &str,
+        f: impl Fn(char) -> Option<T>,
+    ) -> Result<(Self, Markers), ParseError> {
+        let rows = parse::map_rows(input, s)?;
+        let width = rows
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut grid = Self::new(width, rows.len());
+        let mut found = Markers::default();
+        for (y, row) in rows.iter().enumerate() {
+            for (x, (i, ch)) in row.char_indices().enumerate() {
+                let cell = f(ch).ok_or_else(|| ParseError::at(input, &row[i..], "a grid cell"))?;
+                grid.set((x, y), cell);
+                if markers.contains(ch) {
+                    found.positions.push((ch, Vec2i32::new(x as i32, y as i32)));
+                }
+            }
+        }
+        Ok((grid, found))
+    }
+
+    /// Renders the grid as text, mapping each cell with `f`, with a newline after every row.
+    pub fn render(&self, f: impl Fn(&T) -> char) -> String {
+        let mut s = String::with_capacity((self.width() + 1) * self.height());
+        for y in 0..self.height() {
+            s.extend(self.slice(y).iter().map(&f));
+            s.push('\n');
+        }
+        s
     }
 }
 
 impl<T: Default + Debug + Clone> Debug for Array2D<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut list = f.debug_list();
-        for y in 0..self.height {
-            let c1 = y * self.width;
-            let c2 = (y + 1) * self.width;
-            let dbg: Vec<&T> = self.data[c1..c2].iter().collect();
-            list.entry(&dbg);
-        }
-        list.finish()
+        let rows = (0..self.height()).map(|y| self.slice(y));
+        f.debug_list().entries(rows).finish()
     }
 }
@@ -1,95 +1,116 @@
+#![cfg_attr(not(test), allow(dead_code))]
+
 use core::fmt::Debug;
-use std::slice::Iter;
-use std::slice::IterMut;
 
-use crate::vec3::Vec3i32;
+use crate::{
+    array2d::Array2D,
+    arrayn::{ArrayIndex, ArrayN},
+    cuboid::Cuboid,
+    vec3::{vec3, Vec3i32},
+};
 
-#[derive(Clone)]
-pub struct Array3D<T: Default + Clone> {
-    width: usize,
-    height: usize,
-    depth: usize,
-    data: Vec<T>,
-}
+/// A 3D array, stored plane by plane and row by row within a plane.
+pub type Array3D<T> = ArrayN<T, 3>;
 
 impl<T: Default + Clone> Array3D<T> {
     pub fn new(width: usize, height: usize, depth: usize) -> Self {
-        let data = vec![T::default(); width * height * depth];
-        Self {
-            width,
-            height,
-            depth,
-            data,
-        }
+        Self::from_shape([width, height, depth])
+    }
+
+    /// An array with one cell per point of `cuboid`. Point `p` is stored at `p - cuboid.min`.
+    pub fn new_covering(cuboid: &Cuboid<i32>) -> Self {
+        let size = cuboid.size().try_cast::<usize>().unwrap();
+        Self::new(size.x, size.y, size.z)
     }
 
     pub fn width(&self) -> usize {
-        self.width
+        self.shape[0]
     }
 
     pub fn height(&self) -> usize {
-        self.height
+        self.shape[1]
     }
 
     pub fn depth(&self) -> usize {
-        self.depth
+        self.shape[2]
     }
 
-    pub fn fill(&mut self, value: T) {
-        for v in &mut self.data {
-            *v = value.clone();
-        }
+    /// The row at height `y` of plane `z`.
+    pub fn slice(&self, y: usize, z: usize) -> &[T] {
+        let start = (z * self.height() + y) * self.width();
+        &self.data[start..start + self.width()]
     }
 
-    pub fn iter(&self) -> Iter<'_, T> {
-        self.data.iter()
+    pub fn slice_mut(&mut self, y: usize, z: usize) -> &mut [T] {
+        let width = self.width();
+        let start = (z * self.height() + y) * width;
+        &mut self.data[start..start + width]
     }
 
-    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
-        self.data.iter_mut()
+    /// The cells at depth `z`, row by row.
+    pub fn plane(&self, z: usize) -> &[T] {
+        let size = self.width() * self.height();
+        &self.data[z * size..(z + 1) * size]
     }
-}
 
-pub trait Array3DIndex {
-    fn coords(&self) -> (usize, usize, usize);
+    pub fn plane_mut(&mut self, z: usize) -> &mut [T] {
+        let size = self.width() * self.height();
+        &mut self.data[z * size..(z + 1) * size]
+    }
+
+    /// Copies the plane at depth `z` into an `Array2D`.
+    pub fn plane_array(&self, z: usize) -> Array2D<T> {
+        let mut ret = Array2D::new(self.width(), self.height());
+        for (dst, src) in ret.iter_mut().zip(self.plane(z)) {
+            *dst = src.clone();
+        }
+        ret
+    }
 }
 
-impl Array3DIndex for (usize, usize, usize) {
-    fn coords(&self) -> (usize, usize, usize) {
-        *self
+impl ArrayIndex<3> for (usize, usize, usize) {
+    fn position(&self) -> [i64; 3] {
+        [self.0 as i64, self.1 as i64, self.2 as i64]
+    }
+
+    fn coords(&self) -> [usize; 3] {
+        [self.0, self.1, self.2]
     }
 }
 
-impl Array3DIndex for (i32, i32, i32) {
-    fn coords(&self) -> (usize, usize, usize) {
-        (self.0 as usize, self.1 as usize, self.2 as usize)
+impl ArrayIndex<3> for (i32, i32, i32) {
+    fn position(&self) -> [i64; 3] {
+        [self.0 as i64, self.1 as i64, self.2 as i64]
     }
 }
 
-impl Array3DIndex for Vec3i32 {
-    fn coords(&self) -> (usize, usize, usize) {
-        (self.x as usize, self.y as usize, self.z as usize)
+impl ArrayIndex<3> for Vec3i32 {
+    fn position(&self) -> [i64; 3] {
+        self.cast::<i64>().into()
     }
 }
 
 impl<T: Default + Clone> Array3D<T> {
-    pub fn at<P: Array3DIndex>(&self, pos: P) -> &T {
-        let coords = pos.coords();
-        debug_assert!(coords.0 < self.width);
-        debug_assert!(coords.1 < self.height);
-        debug_assert!(coords.2 < self.depth);
-        &self.data[coords.0 + self.width * coords.1 + self.width * self.height * coords.2]
+    /// Iterates over the positions that share a face with `pos` and lie in the array.
+    pub fn neighbors6<P: ArrayIndex<3>>(&self, pos: P) -> impl Iterator<Item = Vec3i32> + '_ {
+        self.neighbors_orthogonal(pos)
+            .map(|[x, y, z]| vec3(x, y, z))
     }
 
-    pub fn at_mut<P: Array3DIndex>(&mut self, pos: P) -> &mut T {
-        let coords = pos.coords();
-        debug_assert!(coords.0 < self.width);
-        debug_assert!(coords.1 < self.height);
-        debug_assert!(coords.2 < self.depth);
-        &mut self.data[coords.0 + self.width * coords.1 + self.width * self.height * coords.2]
+    /// Iterates over the positions that share a face, edge or corner with `pos` and lie in the
+    /// array.
+    pub fn neighbors26<P: ArrayIndex<3>>(&self, pos: P) -> impl Iterator<Item = Vec3i32> + '_ {
+        self.neighbors(pos).map(|[x, y, z]| vec3(x, y, z))
     }
+}
 
-    pub fn set<P: Array3DIndex>(&mut self, pos: P, value: T) {
-        *self.at_mut(pos) = value;
+impl<T: Default + Debug + Clone> Debug for Array3D<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut list = f.debug_list();
+        for z in 0..self.depth() {
+            let plane: Vec<&[T]> = (0..self.height()).map(|y| self.slice(y, z)).collect();
+            list.entry(&plane);
+        }
+        list.finish()
     }
 }
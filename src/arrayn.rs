@@ -0,0 +1,233 @@
+#![cfg_attr(not(test), allow(dead_code))]
+
+use std::slice::Iter;
+use std::slice::IterMut;
+
+/// A dense array with `D` dimensions. The first coordinate varies fastest in memory, so for two
+/// dimensions the cells are stored row by row.
+#[derive(Clone)]
+pub struct ArrayN<T: Default + Clone, const D: usize> {
+    pub(crate) shape: [usize; D],
+    pub(crate) data: Vec<T>,
+}
+
+/// A position in an array with `D` dimensions.
+pub trait ArrayIndex<const D: usize> {
+    /// The position as signed coordinates, which may lie outside of an array.
+    fn position(&self) -> [i64; D];
+
+    /// The position as array coordinates. Panics if it is negative.
+    fn coords(&self) -> [usize; D] {
+        let position = self.position();
+        position.map(|c| usize::try_from(c).unwrap_or_else(|_| negative(&position)))
+    }
+}
+
+fn negative(position: &[i64]) -> ! {
+    let coords: Vec<String> = position.iter().map(|c| c.to_string()).collect();
+    panic!("negative array index ({})", coords.join(", "))
+}
+
+impl<P: ArrayIndex<D>, const D: usize> ArrayIndex<D> for &P {
+    fn position(&self) -> [i64; D] {
+        (*self).position()
+    }
+
+    fn coords(&self) -> [usize; D] {
+        (*self).coords()
+    }
+}
+
+impl<const D: usize> ArrayIndex<D> for [usize; D] {
+    fn position(&self) -> [i64; D] {
+        self.map(|c| c as i64)
+    }
+
+    fn coords(&self) -> [usize; D] {
+        *self
+    }
+}
+
+impl<const D: usize> ArrayIndex<D> for [i32; D] {
+    fn position(&self) -> [i64; D] {
+        self.map(|c| c as i64)
+    }
+}
+
+impl<T: Default + Clone, const D: usize> ArrayN<T, D> {
+    pub fn from_shape(shape: [usize; D]) -> Self {
+        let data = vec![T::default(); shape.iter().product()];
+        Self { shape, data }
+    }
+
+    /// The size along each dimension.
+    pub fn shape(&self) -> [usize; D] {
+        self.shape
+    }
+
+    /// How far apart neighbouring cells along each dimension are in memory.
+    pub fn strides(&self) -> [usize; D] {
+        let mut strides = [1; D];
+        for i in 1..D {
+            strides[i] = strides[i - 1] * self.shape[i - 1];
+        }
+        strides
+    }
+
+    pub fn fill(&mut self, value: T) {
+        for v in &mut self.data {
+            *v = value.clone();
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.data.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.data.iter_mut()
+    }
+
+    /// Builds an array of the same shape with `f` applied to every value.
+    pub fn map<U: Default + Clone>(&self, f: impl Fn(&T) -> U) -> ArrayN<U, D> {
+        ArrayN {
+            shape: self.shape,
+            data: self.data.iter().map(f).collect(),
+        }
+    }
+
+    /// The offset of `coords` in `data`. Panics if they lie outside of the array, which would
+    /// otherwise wrap around into the next row.
+    fn index(&self, coords: [usize; D]) -> usize {
+        let mut index = 0;
+        for i in (0..D).rev() {
+            assert!(
+                coords[i] < self.shape[i],
+                "array index {:?} is out of bounds for shape {:?}",
+                coords,
+                self.shape
+            );
+            index = index * self.shape[i] + coords[i];
+        }
+        index
+    }
+
+    pub fn at<P: ArrayIndex<D>>(&self, pos: P) -> &T {
+        &self.data[self.index(pos.coords())]
+    }
+
+    pub fn at_mut<P: ArrayIndex<D>>(&mut self, pos: P) -> &mut T {
+        let index = self.index(pos.coords());
+        &mut self.data[index]
+    }
+
+    pub fn set<P: ArrayIndex<D>>(&mut self, pos: P, value: T) {
+        *self.at_mut(pos) = value;
+    }
+
+    pub fn in_bounds<P: ArrayIndex<D>>(&self, pos: P) -> bool {
+        let position = pos.position();
+        (0..D).all(|i| position[i] >= 0 && (position[i] as usize) < self.shape[i])
+    }
+
+    /// Returns the value at `pos`, or `None` if it lies outside of the array.
+    pub fn get<P: ArrayIndex<D>>(&self, pos: P) -> Option<&T> {
+        if self.in_bounds(&pos) {
+            Some(self.at(pos))
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut<P: ArrayIndex<D>>(&mut self, pos: P) -> Option<&mut T> {
+        if self.in_bounds(&pos) {
+            Some(self.at_mut(pos))
+        } else {
+            None
+        }
+    }
+
+    /// Iterates over the positions that differ from `pos` by one along a single axis and lie in
+    /// the array: first lower and higher along x, then along y, and so on.
+    pub fn neighbors_orthogonal<P: ArrayIndex<D>>(
+        &self,
+        pos: P,
+    ) -> impl Iterator<Item = [i32; D]> + '_ {
+        let pos = Self::signed(pos);
+        orthogonal_offsets::<D>()
+            .map(move |ofs| add(pos, ofs))
+            .filter(move |pos| self.in_bounds(pos))
+    }
+
+    /// Iterates over all positions around `pos` that lie in the array, including diagonals, with
+    /// x varying fastest.
+    pub fn neighbors<P: ArrayIndex<D>>(&self, pos: P) -> impl Iterator<Item = [i32; D]> + '_ {
+        let pos = Self::signed(pos);
+        all_offsets::<D>()
+            .map(move |ofs| add(pos, ofs))
+            .filter(move |pos| self.in_bounds(pos))
+    }
+
+    /// Sets `value` on every cell that `can_fill` accepts and that is connected to `start` through
+    /// orthogonal neighbours that are accepted as well. `value` itself must not be accepted.
+    /// Returns the number of filled cells.
+    pub fn flood_fill<P: ArrayIndex<D>>(
+        &mut self,
+        start: P,
+        can_fill: impl Fn(&T) -> bool,
+        value: T,
+    ) -> usize {
+        assert!(!can_fill(&value), "flood fill value must not be fillable");
+
+        let mut filled = 0;
+        let mut cells = vec![Self::signed(start)];
+        while let Some(pos) = cells.pop() {
+            if !can_fill(self.at(pos)) {
+                continue;
+            }
+            self.set(pos, value.clone());
+            filled += 1;
+            cells.extend(
+                self.neighbors_orthogonal(pos)
+                    .filter(|next| can_fill(self.at(next))),
+            );
+        }
+        filled
+    }
+
+    /// The position as `i32` coordinates. Panics if a coordinate doesn't fit.
+    pub(crate) fn signed<P: ArrayIndex<D>>(pos: P) -> [i32; D] {
+        pos.position()
+            .map(|c| i32::try_from(c).expect("array position doesn't fit i32"))
+    }
+}
+
+/// The offsets to the 2 * D orthogonal neighbours of a position.
+pub(crate) fn orthogonal_offsets<const D: usize>() -> impl Iterator<Item = [i32; D]> {
+    (0..2 * D).map(|i| {
+        let mut ofs = [0; D];
+        ofs[i / 2] = if i % 2 == 1 { 1 } else { -1 };
+        ofs
+    })
+}
+
+/// The offsets to the 3^D - 1 neighbours of a position, including diagonals.
+pub(crate) fn all_offsets<const D: usize>() -> impl Iterator<Item = [i32; D]> {
+    (0..3usize.pow(D as u32))
+        .map(|mut n| {
+            let mut ofs = [0; D];
+            for c in &mut ofs {
+                *c = (n % 3) as i32 - 1;
+                n /= 3;
+            }
+            ofs
+        })
+        .filter(|ofs| ofs.iter().any(|c| *c != 0))
+}
+
+fn add<const D: usize>(mut pos: [i32; D], ofs: [i32; D]) -> [i32; D] {
+    for i in 0..D {
+        pos[i] += ofs[i];
+    }
+    pos
+}
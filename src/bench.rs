@@ -0,0 +1,226 @@
+use std::{fmt::Write, path::Path, time::Duration};
+
+use crate::{
+    parse::ParseError,
+    solution::Day,
+    timer::{millis, Timer},
+};
+
+/// Statistics over the durations of repeated runs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty());
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let n = sorted.len();
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        };
+
+        let secs: Vec<f64> = sorted.iter().map(|d| d.as_secs_f64()).collect();
+        let mean = secs.iter().sum::<f64>() / n as f64;
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n as f64;
+
+        Self {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// What is measured in a benchmark: parsing, or one of the parts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    Parse,
+    Part(u32),
+}
+
+impl Stage {
+    fn name(&self) -> String {
+        match self {
+            Stage::Parse => String::from("parse"),
+            Stage::Part(part) => format!("task {}", part),
+        }
+    }
+
+    fn key(&self) -> String {
+        match self {
+            Stage::Parse => String::from("parse"),
+            Stage::Part(part) => part.to_string(),
+        }
+    }
+
+    fn from_key(s: &str) -> Option<Self> {
+        match s {
+            "parse" => Some(Stage::Parse),
+            "1" => Some(Stage::Part(1)),
+            "2" => Some(Stage::Part(2)),
+            _ => None,
+        }
+    }
+}
+
+/// The statistics of one stage of one day.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Entry {
+    pub day: u32,
+    pub stage: Stage,
+    pub stats: Stats,
+}
+
+/// Runs a day `warmup` times without measuring, then `runs` times, and returns the statistics
+/// of parsing and each part that ran.
+pub fn bench(
+    solution: &dyn Day,
+    input: &str,
+    part: Option<u32>,
+    warmup: usize,
+    runs: usize,
+) -> Result<Vec<Entry>, ParseError> {
+    for _ in 0..warmup {
+        solution.run(input, part)?;
+    }
+    let timers = (0..runs)
+        .map(|_| solution.run(input, part))
+        .collect::<Result<Vec<Timer>, _>>()?;
+
+    let mut entries = vec![Entry {
+        day: solution.day(),
+        stage: Stage::Parse,
+        stats: Stats::new(
+            &timers
+                .iter()
+                .map(|t| t.parse_duration())
+                .collect::<Vec<_>>(),
+        ),
+    }];
+    for part in [1, 2] {
+        let samples: Vec<Duration> = timers
+            .iter()
+            .filter_map(|t| t.result(part).map(|m| m.duration))
+            .collect();
+        if !samples.is_empty() {
+            entries.push(Entry {
+                day: solution.day(),
+                stage: Stage::Part(part),
+                stats: Stats::new(&samples),
+            });
+        }
+    }
+    Ok(entries)
+}
+
+/// Serializes entries into the baseline format: one line per stage, durations in nanoseconds.
+pub fn to_baseline(entries: &[Entry]) -> String {
+    let mut out = String::from("# day stage min_ns median_ns mean_ns stddev_ns\n");
+    for entry in entries {
+        let stats = &entry.stats;
+        writeln!(
+            out,
+            "{} {} {} {} {} {}",
+            entry.day,
+            entry.stage.key(),
+            stats.min.as_nanos(),
+            stats.median.as_nanos(),
+            stats.mean.as_nanos(),
+            stats.stddev.as_nanos()
+        )
+        .unwrap();
+    }
+    out
+}
+
+pub fn from_baseline(s: &str) -> Result<Vec<Entry>, String> {
+    let mut entries = Vec::new();
+    for (i, line) in s.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let error = || format!("invalid baseline line {}: '{}'", i + 1, line);
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() != 6 {
+            return Err(error());
+        }
+        let nanos = |s: &str| s.parse().map(Duration::from_nanos).map_err(|_| error());
+        entries.push(Entry {
+            day: fields[0].parse().map_err(|_| error())?,
+            stage: Stage::from_key(fields[1]).ok_or_else(error)?,
+            stats: Stats {
+                min: nanos(fields[2])?,
+                median: nanos(fields[3])?,
+                mean: nanos(fields[4])?,
+                stddev: nanos(fields[5])?,
+            },
+        });
+    }
+    Ok(entries)
+}
+
+pub fn save_baseline(path: &Path, entries: &[Entry]) -> Result<(), String> {
+    std::fs::write(path, to_baseline(entries))
+        .map_err(|err| format!("can't write {}: {}", path.display(), err))
+}
+
+pub fn load_baseline(path: &Path) -> Result<Vec<Entry>, String> {
+    let s = std::fs::read_to_string(path)
+        .map_err(|err| format!("can't read {}: {}", path.display(), err))?;
+    from_baseline(&s)
+}
+
+/// Prints a table of the entries; with a baseline, also the change of the median against it.
+pub fn print(entries: &[Entry], baseline: Option<&[Entry]>) {
+    print!(
+        "{:>5} {:>8} {:>12} {:>12} {:>12} {:>12}",
+        "day", "stage", "min ms", "median ms", "mean ms", "stddev ms"
+    );
+    if baseline.is_some() {
+        print!(" {:>12} {:>9}", "base ms", "change");
+    }
+    println!();
+
+    for entry in entries {
+        let stats = &entry.stats;
+        print!(
+            "{:>5} {:>8} {:>12.3} {:>12.3} {:>12.3} {:>12.3}",
+            format!("{:02}", entry.day),
+            entry.stage.name(),
+            millis(stats.min),
+            millis(stats.median),
+            millis(stats.mean),
+            millis(stats.stddev)
+        );
+        if let Some(baseline) = baseline {
+            let base = baseline
+                .iter()
+                .find(|base| base.day == entry.day && base.stage == entry.stage);
+            match base {
+                Some(base) => {
+                    let before = base.stats.median.as_secs_f64();
+                    let after = stats.median.as_secs_f64();
+                    let change = if before > 0.0 {
+                        format!("{:+.1}%", (after - before) / before * 100.0)
+                    } else {
+                        String::from("---")
+                    };
+                    print!(" {:>12.3} {:>9}", millis(base.stats.median), change);
+                }
+                None => print!(" {:>12} {:>9}", "---", "---"),
+            }
+        }
+        println!();
+    }
+}
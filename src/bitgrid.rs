@@ -0,0 +1,239 @@
+#![cfg_attr(not(test), allow(dead_code))]
+
+use std::ops::Range;
+
+use crate::arrayn::ArrayIndex;
+
+/// A 2D grid of booleans, packed into 64 bit words. Every row starts at a new word, with bit `x`
+/// of the row holding the cell at column `x`.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    data: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        let words_per_row = width.div_ceil(64);
+        Self {
+            width,
+            height,
+            words_per_row,
+            data: vec![0; words_per_row * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn fill(&mut self, value: bool) {
+        let word = if value { u64::MAX } else { 0 };
+        self.data.fill(word);
+        if value {
+            for y in 0..self.height {
+                self.clear_padding(y);
+            }
+        }
+    }
+
+    pub fn in_bounds<P: ArrayIndex<2>>(&self, pos: P) -> bool {
+        let [x, y] = pos.position();
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    pub fn at<P: ArrayIndex<2>>(&self, pos: P) -> bool {
+        let [x, y] = pos.coords();
+        self.check_bounds(x, y);
+        let word = self.data[y * self.words_per_row + x / 64];
+        word & (1 << (x % 64)) != 0
+    }
+
+    /// Returns the value at `pos`, or `None` if it lies outside of the grid.
+    pub fn get<P: ArrayIndex<2>>(&self, pos: P) -> Option<bool> {
+        if self.in_bounds(&pos) {
+            Some(self.at(pos))
+        } else {
+            None
+        }
+    }
+
+    pub fn set<P: ArrayIndex<2>>(&mut self, pos: P, value: bool) {
+        let [x, y] = pos.coords();
+        self.check_bounds(x, y);
+        let word = &mut self.data[y * self.words_per_row + x / 64];
+        if value {
+            *word |= 1 << (x % 64);
+        } else {
+            *word &= !(1 << (x % 64));
+        }
+    }
+
+    /// The words of row `y`, with the first column in the lowest bit of the first word.
+    pub fn row_words(&self, y: usize) -> &[u64] {
+        &self.data[self.row_range(y)]
+    }
+
+    /// The words of all rows, one row after the other.
+    pub(crate) fn words(&self) -> &[u64] {
+        &self.data
+    }
+
+    /// The words of all rows. Bits beyond the width have to stay clear.
+    pub(crate) fn words_mut(&mut self) -> &mut [u64] {
+        &mut self.data
+    }
+
+    /// Row `y` as an integer, for grids that are at most 64 cells wide.
+    pub fn row(&self, y: usize) -> u64 {
+        self.check_word_rows();
+        self.data[self.row_range(y)].first().copied().unwrap_or(0)
+    }
+
+    /// Sets row `y` from an integer, for grids that are at most 64 cells wide. Bits past the
+    /// width are dropped.
+    pub fn set_row(&mut self, y: usize, bits: u64) {
+        self.check_word_rows();
+        let range = self.row_range(y);
+        if let Some(word) = self.data[range].first_mut() {
+            *word = bits;
+            self.clear_padding(y);
+        }
+    }
+
+    pub fn or_row(&mut self, y: usize, bits: u64) {
+        let bits = self.row(y) | bits;
+        self.set_row(y, bits);
+    }
+
+    pub fn and_row(&mut self, y: usize, bits: u64) {
+        let bits = self.row(y) & bits;
+        self.set_row(y, bits);
+    }
+
+    /// Moves the cells of row `y` by `n` columns towards higher x. Cells pushed past the right
+    /// edge are dropped.
+    pub fn shl_row(&mut self, y: usize, n: usize) {
+        let range = self.row_range(y);
+        let row = &mut self.data[range];
+        let (words, bits) = (n / 64, n % 64);
+        for i in (0..row.len()).rev() {
+            let lo = i.checked_sub(words).map_or(0, |j| row[j]);
+            let carry = i.checked_sub(words + 1).map_or(0, |j| row[j]);
+            row[i] = if bits == 0 {
+                lo
+            } else {
+                (lo << bits) | (carry >> (64 - bits))
+            };
+        }
+        self.clear_padding(y);
+    }
+
+    /// Moves the cells of row `y` by `n` columns towards lower x. Cells pushed past the left edge
+    /// are dropped.
+    pub fn shr_row(&mut self, y: usize, n: usize) {
+        let range = self.row_range(y);
+        let row = &mut self.data[range];
+        let (words, bits) = (n / 64, n % 64);
+        for i in 0..row.len() {
+            let hi = row.get(i + words).copied().unwrap_or(0);
+            let carry = row.get(i + words + 1).copied().unwrap_or(0);
+            row[i] = if bits == 0 {
+                hi
+            } else {
+                (hi >> bits) | (carry << (64 - bits))
+            };
+        }
+    }
+
+    /// The number of set cells in row `y`.
+    pub fn row_count_ones(&self, y: usize) -> usize {
+        let ones: u32 = self.row_words(y).iter().map(|w| w.count_ones()).sum();
+        ones as usize
+    }
+
+    /// The number of set cells in the grid.
+    pub fn count_ones(&self) -> usize {
+        let ones: u32 = self.data.iter().map(|w| w.count_ones()).sum();
+        ones as usize
+    }
+
+    /// Copies the rows in `range` into a new grid.
+    pub fn crop_rows(&self, range: Range<usize>) -> Self {
+        assert!(
+            range.start <= range.end && range.end <= self.height,
+            "rows {:?} are out of bounds for height {}",
+            range,
+            self.height
+        );
+        let start = range.start * self.words_per_row;
+        let end = range.end * self.words_per_row;
+        Self {
+            height: range.len(),
+            data: self.data[start..end].to_vec(),
+            ..*self
+        }
+    }
+
+    /// Iterates over all cells, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.height).flat_map(move |y| (0..self.width).map(move |x| self.at((x, y))))
+    }
+
+    /// Draws the grid with `on` and `off` characters, with a newline after each row.
+    pub fn render(&self, on: char, off: char) -> String {
+        let mut ret = String::with_capacity((self.width + 1) * self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                ret.push(if self.at((x, y)) { on } else { off });
+            }
+            ret.push('\n');
+        }
+        ret
+    }
+
+    fn check_bounds(&self, x: usize, y: usize) {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is out of bounds for a {}x{} grid",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+    }
+
+    fn check_word_rows(&self) {
+        assert!(
+            self.width <= 64,
+            "rows of width {} don't fit a word",
+            self.width
+        );
+    }
+
+    /// The range of `data` holding row `y`.
+    fn row_range(&self, y: usize) -> Range<usize> {
+        assert!(
+            y < self.height,
+            "row {} is out of bounds for height {}",
+            y,
+            self.height
+        );
+        y * self.words_per_row..(y + 1) * self.words_per_row
+    }
+
+    /// Clears the bits past the last column of row `y`, so that whole-word operations never see
+    /// them set.
+    fn clear_padding(&mut self, y: usize) {
+        let bits = self.width % 64;
+        if bits != 0 {
+            self.data[(y + 1) * self.words_per_row - 1] &= (1 << bits) - 1;
+        }
+    }
+}
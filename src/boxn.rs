@@ -0,0 +1,88 @@
+//! The shared definition of the axis-aligned box types `Rect` and `Cuboid`.
+
+/// Defines a box type over integer points of the vector type `$vec`, with `$measure` naming the
+/// method that counts the points in it.
+macro_rules! box_type {
+    ($name:ident, $vec:ident, $measure:ident, $($field:ident),+) => {
+        /// An axis-aligned box of integer points, with both corners included.
+        #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct $name<T> {
+            pub min: $vec<T>,
+            pub max: $vec<T>,
+        }
+
+        impl<T: num::PrimInt> $name<T> {
+            /// The box between two corners. Panics if `min` lies above `max` along any axis.
+            pub fn new(min: $vec<T>, max: $vec<T>) -> Self {
+                assert!(min.le(max).all(), "box corners are out of order");
+                Self { min, max }
+            }
+
+            /// The smallest box containing all of `points`, or `None` if there are none.
+            pub fn from_points(points: impl IntoIterator<Item = $vec<T>>) -> Option<Self> {
+                let mut points = points.into_iter();
+                let first = points.next()?;
+                Some(points.fold(Self { min: first, max: first }, |ret, point| {
+                    Self { min: ret.min.min(point), max: ret.max.max(point) }
+                }))
+            }
+
+            /// The number of points along each axis.
+            pub fn size(&self) -> $vec<T> {
+                self.max - self.min + T::one()
+            }
+
+            pub fn $measure(&self) -> T {
+                self.size().product()
+            }
+
+            pub fn contains(&self, point: $vec<T>) -> bool {
+                self.min.le(point).all() && point.le(self.max).all()
+            }
+
+            /// The points in both boxes, or `None` if they don't overlap.
+            pub fn intersect(&self, other: &Self) -> Option<Self> {
+                let min = self.min.max(other.min);
+                let max = self.max.min(other.max);
+                min.le(max).all().then_some(Self { min, max })
+            }
+
+            /// The smallest box containing both boxes.
+            pub fn union(&self, other: &Self) -> Self {
+                Self {
+                    min: self.min.min(other.min),
+                    max: self.max.max(other.max),
+                }
+            }
+
+            /// Grows the box by `margin` points on every side.
+            pub fn expand(&self, margin: T) -> Self {
+                Self::new(self.min - margin, self.max + margin)
+            }
+
+            /// Iterates over all points in the box, with x varying fastest.
+            pub fn points(&self) -> impl Iterator<Item = $vec<T>> {
+                let min = self.min;
+                let size = self.size().map(|c| c.to_usize().unwrap());
+                (0..size.product()).map(move |mut n| $vec {
+                    $($field: {
+                        let c = min.$field + T::from(n % size.$field).unwrap();
+                        n /= size.$field;
+                        c
+                    }),+
+                })
+            }
+        }
+
+        impl<T: num::Num + std::fmt::Debug> std::fmt::Debug for $name<T> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("min", &self.min)
+                    .field("max", &self.max)
+                    .finish()
+            }
+        }
+    };
+}
+
+pub(crate) use box_type;
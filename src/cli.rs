@@ -0,0 +1,174 @@
+use std::{ops::RangeInclusive, path::PathBuf};
+
+use crate::report::Format;
+
+pub const DAYS: u32 = 25;
+
+pub const USAGE: &str = "usage: aoc [DAYS] [--input PATH] [--part 1|2] [--format text|json|csv]
+       aoc bench [DAYS] [--input PATH] [--part 1|2] [--runs N] [--warmup N]
+                 [--save PATH] [--baseline PATH]
+
+  DAYS          a single day (17), a range (3-9) or all (default)
+  --input PATH  read the puzzle input from PATH instead of the bundled one;
+                when running several days, PATH is a directory with N.txt files
+  --part 1|2    only run the given part
+  --format FMT  print answers and timings as text (default), json or csv
+
+bench runs each day repeatedly and reports min/median/mean/stddev per part:
+  --runs N         number of measured runs (default 10)
+  --warmup N       number of unmeasured runs before measuring (default 1)
+  --save PATH      write the results to PATH as a baseline
+  --baseline PATH  compare the median of each part to a saved baseline
+
+set AOC_FRAMES=DIR to write the simulations of days 14, 17, 22 and 23 as numbered
+PPM/PGM images to DIR, AOC_MESH=DIR to write the surface of day 18's droplet as
+OBJ and STL files to DIR, and AOC_SNAPSHOTS=DIR to write day 17's board after
+part 1 as a binary snapshot to DIR; these are written after the timed parts, and
+not by bench";
+
+/// Options of the `bench` subcommand.
+pub struct BenchArgs {
+    pub runs: usize,
+    pub warmup: usize,
+    pub save: Option<PathBuf>,
+    pub baseline: Option<PathBuf>,
+}
+
+impl Default for BenchArgs {
+    fn default() -> Self {
+        Self {
+            runs: 10,
+            warmup: 1,
+            save: None,
+            baseline: None,
+        }
+    }
+}
+
+pub struct Args {
+    pub days: RangeInclusive<u32>,
+    pub input: Option<PathBuf>,
+    pub part: Option<u32>,
+    pub format: Format,
+    pub bench: Option<BenchArgs>,
+}
+
+fn parse_day(s: &str) -> Result<u32, String> {
+    match s.parse() {
+        Ok(day) if (1..=DAYS).contains(&day) => Ok(day),
+        _ => Err(format!("invalid day '{}', expected 1..{}", s, DAYS)),
+    }
+}
+
+fn parse_count(name: &str, s: &str) -> Result<usize, String> {
+    s.parse()
+        .map_err(|_| format!("invalid value '{}' for {}, expected a number", s, name))
+}
+
+fn parse_days(s: &str) -> Result<RangeInclusive<u32>, String> {
+    if s == "all" {
+        return Ok(1..=DAYS);
+    }
+    match s.split_once('-') {
+        Some((from, to)) => {
+            let from = parse_day(from)?;
+            let to = parse_day(to)?;
+            if from > to {
+                return Err(format!("invalid day range '{}'", s));
+            }
+            Ok(from..=to)
+        }
+        None => {
+            let day = parse_day(s)?;
+            Ok(day..=day)
+        }
+    }
+}
+
+impl Args {
+    pub fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Self, String> {
+        let mut days = None;
+        let mut input = None;
+        let mut part = None;
+        let mut format = Format::Text;
+        let mut is_bench = false;
+        let mut bench_args = BenchArgs::default();
+        let mut bench_option = None;
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {}", name))
+            };
+            match arg.as_str() {
+                "--input" | "-i" => input = Some(PathBuf::from(value("--input")?)),
+                "--part" | "-p" => {
+                    part = match value("--part")?.as_str() {
+                        "1" => Some(1),
+                        "2" => Some(2),
+                        other => return Err(format!("invalid part '{}', expected 1 or 2", other)),
+                    }
+                }
+                "--format" | "-f" => format = value("--format")?.parse()?,
+                "--runs" => {
+                    bench_args.runs = parse_count("--runs", &value("--runs")?)?;
+                    if bench_args.runs == 0 {
+                        return Err(String::from("--runs must be at least 1"));
+                    }
+                    bench_option = Some("--runs");
+                }
+                "--warmup" => {
+                    bench_args.warmup = parse_count("--warmup", &value("--warmup")?)?;
+                    bench_option = Some("--warmup");
+                }
+                "--save" => {
+                    bench_args.save = Some(PathBuf::from(value("--save")?));
+                    bench_option = Some("--save");
+                }
+                "--baseline" => {
+                    bench_args.baseline = Some(PathBuf::from(value("--baseline")?));
+                    bench_option = Some("--baseline");
+                }
+                "--help" | "-h" => return Err(String::new()),
+                _ if arg.starts_with('-') => return Err(format!("unknown option '{}'", arg)),
+                "bench" if !is_bench && days.is_none() => is_bench = true,
+                _ if days.is_none() => days = Some(parse_days(&arg)?),
+                _ => return Err(format!("unexpected argument '{}'", arg)),
+            }
+        }
+
+        let bench = match (is_bench, bench_option) {
+            (true, _) => Some(bench_args),
+            (false, Some(option)) => return Err(format!("{} is only valid with bench", option)),
+            (false, None) => None,
+        };
+
+        let days = days.unwrap_or(1..=DAYS);
+        if let Some(path) = &input {
+            if days.start() != days.end() && !path.is_dir() {
+                return Err(String::from(
+                    "--input must be a directory when running more than one day",
+                ));
+            }
+        }
+
+        Ok(Self {
+            days,
+            input,
+            part,
+            format,
+            bench,
+        })
+    }
+
+    /// Returns the path of the input file to use for the given day, if overridden.
+    pub fn input_path(&self, day: u32) -> Option<PathBuf> {
+        self.input.as_ref().map(|path| {
+            if path.is_dir() {
+                path.join(format!("{}.txt", day))
+            } else {
+                path.clone()
+            }
+        })
+    }
+}
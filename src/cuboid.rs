@@ -0,0 +1,5 @@
+#![cfg_attr(not(test), allow(dead_code))]
+
+use crate::{boxn::box_type, vec3::Vec3};
+
+box_type!(Cuboid, Vec3, volume, x, y, z);
@@ -1,23 +1,38 @@
-pub fn solve() {
-    let mut elves = Vec::new();
+use crate::{
+    parse::{self, ParseError},
+    solution::Solution,
+};
 
-    let input = String::from(include_str!("inputs/1.txt"));
-    for line in input.split('\n') {
-        if line.is_empty() {
-            elves.push(Vec::new());
-        } else if let Some(num) = line.parse::<i32>().ok() {
-            if let Some(elf) = elves.last_mut() {
-                elf.push(num);
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u32 = 1;
+    const INPUT: &'static str = include_str!("inputs/1.txt");
+
+    type Input = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+        let mut elves = vec![Vec::new()];
+        for line in input.split('\n') {
+            if line.is_empty() {
+                elves.push(Vec::new());
+            } else {
+                elves.last_mut().unwrap().push(parse::number(input, line)?);
             }
         }
-    }
 
-    let mut calories: Vec<i32> = elves.iter().map(|food| food.iter().sum()).collect();
-    calories.sort();
+        let mut calories: Vec<i32> = elves.iter().map(|food| food.iter().sum()).collect();
+        calories.sort();
+        Ok(calories)
+    }
 
-    let top1 = calories.iter().max().unwrap();
-    let top3: i32 = calories.iter().rev().take(3).sum();
+    fn part1(calories: &Vec<i32>) -> i32 {
+        *calories.iter().max().unwrap()
+    }
 
-    println!("[day 01] task 1: {}", top1);
-    println!("[day 01] task 2: {}", top3);
+    fn part2(calories: &Vec<i32>) -> i32 {
+        calories.iter().rev().take(3).sum()
+    }
 }
@@ -1,38 +1,66 @@
-pub fn solve() {
-    let data = include_str!("inputs/10.txt");
-    let lines: Vec<&str> = data.split('\n').filter(|s| !s.trim().is_empty()).collect();
-
-    let mut values = vec![1];
-    let mut x = 1;
-
-    for line in lines {
-        if line == "noop" {
-            values.push(x);
-        } else {
-            let mut parts = line.split(' ');
-            let cmd = parts.next().unwrap();
-            assert!(cmd == "addx");
-            let arg = parts.next().unwrap().parse::<i32>().unwrap();
-            values.push(x);
-            values.push(x);
-            x += arg;
+use crate::{
+    parse::{self, ParseError},
+    solution::Solution,
+};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    const INPUT: &'static str = include_str!("inputs/10.txt");
+
+    type Input = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+        let lines: Vec<&str> = input.split('\n').filter(|s| !s.trim().is_empty()).collect();
+
+        let mut values = vec![1];
+        let mut x = 1;
+
+        for line in lines {
+            if line == "noop" {
+                values.push(x);
+            } else {
+                let arg = parse::prefix(input, line, "addx ")?;
+                let arg: i32 = parse::number(input, arg)?;
+                values.push(x);
+                values.push(x);
+                x += arg;
+            }
+        }
+
+        // The CRT draws 240 pixels, one per cycle, and `values[0]` is the value before the first.
+        if values.len() <= 240 {
+            return Err(ParseError::after(
+                input,
+                input.trim_end(),
+                "a program running for at least 240 cycles",
+            ));
         }
-    }
 
-    let mut task1 = 0;
-    for i in [20, 60, 100, 140, 180, 220] {
-        task1 += i as i32 * values[i];
+        Ok(values)
     }
 
-    println!("[day 10] task 1: {}", task1);
+    fn part1(values: &Vec<i32>) -> i32 {
+        let mut task1 = 0;
+        for i in [20, 60, 100, 140, 180, 220] {
+            task1 += i as i32 * values[i];
+        }
+        task1
+    }
 
-    for y in 0..6 {
-        let mut line = String::new();
-        for x in 0..40 {
-            let v = values[y * 40 + x + 1];
-            let visible = (v - x as i32).abs() < 2;
-            line += if visible { "##" } else { ".." };
+    fn part2(values: &Vec<i32>) -> String {
+        let mut task2 = String::new();
+        for y in 0..6 {
+            for x in 0..40 {
+                let v = values[y * 40 + x + 1];
+                let visible = (v - x as i32).abs() < 2;
+                task2 += if visible { "##" } else { ".." };
+            }
+            task2.push('\n');
         }
-        println!("[day 10] task 2: {}", line);
+        task2
     }
 }
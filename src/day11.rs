@@ -1,3 +1,8 @@
+use crate::{
+    parse::{self, ParseError},
+    solution::Solution,
+};
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum Operation {
     Plus,
@@ -5,7 +10,7 @@ enum Operation {
 }
 
 #[derive(Clone)]
-struct Monkey {
+pub struct Monkey {
     items: Vec<i64>,
     op_type: Operation,
     op_args: (i64, i64),
@@ -14,107 +19,150 @@ struct Monkey {
     if_false: usize,
 }
 
+/// Parses an operand of the operation, with -1 standing for the old value.
+fn parse_operand(input: &str, s: &str) -> Result<i64, ParseError> {
+    if s == "old" {
+        Ok(-1)
+    } else {
+        parse::number(input, s)
+    }
+}
+
+/// Parses the target of a throw, which has to be one of the `count` monkeys.
+fn parse_target(input: &str, s: &str, count: usize) -> Result<usize, ParseError> {
+    match s.parse() {
+        Ok(target) if target < count => Ok(target),
+        _ => Err(ParseError::at(
+            input,
+            s,
+            format!("a monkey below {}", count),
+        )),
+    }
+}
+
 impl Monkey {
-    fn parse(lines: &[&str]) -> Self {
-        assert!(lines.len() == 6);
-        let items = lines[1][18..]
-            .split(", ")
-            .map(|s| s.parse().unwrap())
-            .collect();
-        let mut ops = lines[2][19..].split(" ");
-        let arg1 = ops.next().unwrap();
-        let op = ops.next().unwrap();
-        let arg2 = ops.next().unwrap();
-        let arg1 = if arg1 == "old" {
-            -1
-        } else {
-            arg1.parse().unwrap()
-        };
-        let arg2 = if arg2 == "old" {
-            -1
-        } else {
-            arg2.parse().unwrap()
+    fn parse(input: &str, block: &str, count: usize) -> Result<Self, ParseError> {
+        let mut lines = parse::lines(block);
+        let mut line = |pattern| {
+            let line = parse::next(input, block, &mut lines, "another line")?;
+            parse::pattern::<1>(input, line, pattern).map(|[field]| field)
         };
+        line("Monkey {}:")?;
+        let items = line("Starting items: {}")?;
+        let operation = line("Operation: new = {}")?;
+        let div = line("Test: divisible by {}")?;
+        let if_true = line("If true: throw to monkey {}")?;
+        let if_false = line("If false: throw to monkey {}")?;
+
+        let items = items
+            .split(", ")
+            .map(|s| parse::number(input, s))
+            .collect::<Result<_, _>>()?;
+
+        let [arg1, op, arg2] = parse::pattern(input, operation, "{} {} {}")?;
         let op = match op {
             "*" => Operation::Mul,
             "+" => Operation::Plus,
-            _ => panic!("nay"),
+            _ => return Err(ParseError::at(input, op, "'*' or '+'")),
         };
-        let div = lines[3][21..].parse().unwrap();
-        let if_true = lines[4][29..].parse().unwrap();
-        let if_false = lines[5][30..].parse().unwrap();
-        Self {
+
+        Ok(Self {
             items,
             op_type: op,
-            op_args: (arg1, arg2),
-            div,
-            if_true,
-            if_false,
-        }
+            op_args: (parse_operand(input, arg1)?, parse_operand(input, arg2)?),
+            div: match div.parse() {
+                Ok(div) if div > 0 => div,
+                _ => return Err(ParseError::at(input, div, "a positive number")),
+            },
+            if_true: parse_target(input, if_true, count)?,
+            if_false: parse_target(input, if_false, count)?,
+        })
     }
 }
 
-pub fn solve() {
-    let data = include_str!("inputs/11.txt").trim();
-    let lines: Vec<&str> = data.split('\n').collect();
-
-    let mut monkeys = Vec::new();
+fn monkey_business(monkeys: &[Monkey], rounds: usize, relief: bool) -> usize {
     let mut all_div = 1;
-    for i in 0..((lines.len() + 1) / 7) {
-        monkeys.push(Monkey::parse(&lines[7 * i..7 * i + 6]));
-        if all_div % monkeys[i].div != 0 {
-            all_div *= monkeys[i].div;
+    for monkey in monkeys {
+        if all_div % monkey.div != 0 {
+            all_div *= monkey.div;
         }
     }
 
-    let mut monkeys = vec![monkeys.clone(), monkeys.clone()];
-
-    let mut tasks = [0; 2];
-    for task in 0..2 {
-        let mut inspectations = vec![0; monkeys[task].len()];
-        let rounds = [20, 10000][task];
-
-        for _ in 0..rounds {
-            for i in 0..monkeys[task].len() {
-                let if_true = monkeys[task][i].if_true;
-                let if_false = monkeys[task][i].if_false;
-                let op_args = monkeys[task][i].op_args;
-                let op_type = monkeys[task][i].op_type;
-                let div = monkeys[task][i].div;
-
-                inspectations[i] += monkeys[task][i].items.len();
-
-                // assign new priorities
-                for item in &mut monkeys[task][i].items {
-                    let a1 = if op_args.0 >= 0 { op_args.0 } else { *item };
-                    let a2 = if op_args.1 >= 0 { op_args.1 } else { *item };
-                    *item = match op_type {
-                        Operation::Mul => a1 * a2,
-                        Operation::Plus => a1 + a2,
-                    };
-                    if task == 0 {
-                        *item /= 3;
-                    }
-                    *item = *item % all_div;
+    let mut monkeys = monkeys.to_vec();
+    let mut inspectations = vec![0; monkeys.len()];
+
+    for _ in 0..rounds {
+        for i in 0..monkeys.len() {
+            let if_true = monkeys[i].if_true;
+            let if_false = monkeys[i].if_false;
+            let op_args = monkeys[i].op_args;
+            let op_type = monkeys[i].op_type;
+            let div = monkeys[i].div;
+
+            inspectations[i] += monkeys[i].items.len();
+
+            // assign new priorities
+            for item in &mut monkeys[i].items {
+                let a1 = if op_args.0 >= 0 { op_args.0 } else { *item };
+                let a2 = if op_args.1 >= 0 { op_args.1 } else { *item };
+                *item = match op_type {
+                    Operation::Mul => a1 * a2,
+                    Operation::Plus => a1 + a2,
+                };
+                if relief {
+                    *item /= 3;
                 }
+                *item %= all_div;
+            }
 
-                let items = monkeys[task][i].items.split_off(0);
-                for item in &items {
-                    if item % div == 0 {
-                        monkeys[task][if_true].items.push(*item);
-                    } else {
-                        monkeys[task][if_false].items.push(*item);
-                    }
+            let items = monkeys[i].items.split_off(0);
+            for item in &items {
+                if item % div == 0 {
+                    monkeys[if_true].items.push(*item);
+                } else {
+                    monkeys[if_false].items.push(*item);
                 }
             }
         }
+    }
+
+    inspectations.sort();
+    inspectations.reverse();
+    inspectations[0] * inspectations[1]
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+    const INPUT: &'static str = include_str!("inputs/11.txt");
+
+    type Input = Vec<Monkey>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Monkey>, ParseError> {
+        let blocks: Vec<&str> = parse::paragraphs(input).collect();
+        let monkeys: Vec<Monkey> = blocks
+            .iter()
+            .map(|block| Monkey::parse(input, block, blocks.len()))
+            .collect::<Result<_, _>>()?;
+        // The monkey business multiplies the inspections of the two most active monkeys.
+        if monkeys.len() < 2 {
+            return Err(ParseError::after(
+                input,
+                input.trim_end(),
+                "at least 2 monkeys",
+            ));
+        }
+        Ok(monkeys)
+    }
 
-        inspectations.sort();
-        inspectations.reverse();
-        let _ = inspectations.split_off(2);
-        tasks[task] = inspectations[0] * inspectations[1];
+    fn part1(monkeys: &Vec<Monkey>) -> usize {
+        monkey_business(monkeys, 20, true)
     }
 
-    println!("[day 11] task 1: {}", tasks[0]);
-    println!("[day 11] task 2: {}", tasks[1]);
+    fn part2(monkeys: &Vec<Monkey>) -> usize {
+        monkey_business(monkeys, 10000, false)
+    }
 }
@@ -1,63 +1,41 @@
-use crate::array2d::Array2D;
+use crate::{
+    array2d::Array2D,
+    bitgrid::BitGrid,
+    parse::{self, ParseError},
+    solution::Solution,
+    vec2::Vec2i32,
+};
 
-pub fn solve() {
-    let data = include_str!("inputs/12.txt");
-    let lines: Vec<&str> = data.split('\n').filter(|s| !s.trim().is_empty()).collect();
-
-    let width = lines[0].len();
-    let height = lines.len();
-
-    let mut heights = Array2D::new(width, height);
-    let mut path = Array2D::new(width, height);
-    let mut start = (0, 0);
-    let mut end = (0, 0);
-    for j in 0..height {
-        for i in 0..width {
-            let mut ch = lines[j].chars().nth(i).unwrap();
-            if ch == 'S' {
-                start = (i, j);
-                ch = 'a';
-            }
-            if ch == 'E' {
-                end = (i, j);
-                ch = 'z';
-            }
-            heights.set((i, j), ch as usize - 'a' as usize);
-            path.set((i, j), usize::MAX);
-        }
-    }
+pub struct HeightMap {
+    heights: Array2D<usize>,
+    start: Vec2i32,
+    end: Vec2i32,
+}
 
-    path.set(end, 0);
+/// Returns the length of the shortest path from each position to the end.
+fn distances_to_end(map: &HeightMap) -> Array2D<usize> {
+    let heights = &map.heights;
+    let mut path = Array2D::new(heights.width(), heights.height());
+    path.fill(usize::MAX);
+    path.set(map.end, 0);
+    let mut visited = BitGrid::new(heights.width(), heights.height());
+    visited.set(map.end, true);
 
     let mut curr = 0;
-    let mut curr_path = vec![end];
+    let mut curr_path = vec![map.end];
 
     loop {
         let mut next_path = Vec::new();
         curr += 1;
 
-        let mut try_go = |pos, from_height, next: &mut Vec<(usize, usize)>| {
-            if *path.at(pos) == usize::MAX && *heights.at(pos) + 1 >= from_height {
-                path.set(pos, curr);
-                if !next.contains(&pos) {
-                    next.push(pos);
-                }
-            }
-        };
-
         for pos in &curr_path {
-            let h = *heights.at(*pos);
-            if pos.0 > 0 {
-                try_go((pos.0 - 1, pos.1), h, &mut next_path);
-            }
-            if pos.0 < width - 1 {
-                try_go((pos.0 + 1, pos.1), h, &mut next_path);
-            }
-            if pos.1 > 0 {
-                try_go((pos.0, pos.1 - 1), h, &mut next_path);
-            }
-            if pos.1 < height - 1 {
-                try_go((pos.0, pos.1 + 1), h, &mut next_path);
+            let h = *heights.at(pos);
+            for next in heights.neighbors4(pos) {
+                if !visited.at(next) && *heights.at(next) + 1 >= h {
+                    visited.set(next, true);
+                    path.set(next, curr);
+                    next_path.push(next);
+                }
             }
         }
 
@@ -67,15 +45,53 @@ pub fn solve() {
         curr_path = next_path;
     }
 
-    let mut task2 = usize::MAX;
-    for y in 0..height {
-        for x in 0..width {
-            if *heights.at((x, y)) == 0 {
-                task2 = task2.min(*path.at((x, y)));
-            }
+    path
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+    const INPUT: &'static str = include_str!("inputs/12.txt");
+
+    type Input = HeightMap;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<HeightMap, ParseError> {
+        let (heights, markers) =
+            parse::grid(input, input, "a height, S or E", "SE", |ch| match ch {
+                'S' => Some(0),
+                'E' => Some(25),
+                'a'..='z' => Some(ch as usize - 'a' as usize),
+                _ => None,
+            })?;
+
+        match (markers.get('S'), markers.get('E')) {
+            (None, _) => Err(ParseError::after(input, input.trim_end(), "a start S")),
+            (_, None) => Err(ParseError::after(input, input.trim_end(), "an end E")),
+            (Some(start), Some(end)) => Ok(HeightMap {
+                heights,
+                start,
+                end,
+            }),
         }
     }
 
-    println!("[day 12] task 1: {}", path.at(start));
-    println!("[day 12] task 2: {}", task2);
+    fn part1(map: &HeightMap) -> usize {
+        *distances_to_end(map).at(map.start)
+    }
+
+    fn part2(map: &HeightMap) -> usize {
+        let path = distances_to_end(map);
+        let mut task2 = usize::MAX;
+        for y in 0..map.heights.height() {
+            for x in 0..map.heights.width() {
+                if *map.heights.at((x, y)) == 0 {
+                    task2 = task2.min(*path.at((x, y)));
+                }
+            }
+        }
+        task2
+    }
 }
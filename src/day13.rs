@@ -1,7 +1,12 @@
 use std::cmp::Ordering;
 
+use crate::{
+    parse::{self, ParseError},
+    solution::Solution,
+};
+
 #[derive(Clone, PartialEq, Eq)]
-enum Value {
+pub enum Value {
     Integer(i32),
     List(Vec<Value>),
 }
@@ -15,14 +20,14 @@ impl Value {
                 return cmp;
             }
         }
-        return lhs.len().cmp(&rhs.len());
+        lhs.len().cmp(&rhs.len())
     }
 
     fn compare(&self, other: &Value) -> Ordering {
         match self {
             Self::Integer(i) => match other {
                 Self::Integer(j) => i.cmp(j),
-                Self::List(l) => Self::compare_lists(&[Self::Integer(*i)], &l),
+                Self::List(l) => Self::compare_lists(&[Self::Integer(*i)], l),
             },
             Self::List(l) => match other {
                 Self::Integer(j) => Self::compare_lists(l, &[Self::Integer(*j)]),
@@ -32,56 +37,51 @@ impl Value {
     }
 
     fn parse_str(s: &str) -> Self {
-        let chars: Vec<char> = s.trim().chars().collect();
-        Self::parse(&chars).0
+        Self::parse_line(s, s.trim()).unwrap()
     }
 
-    fn parse(s: &[char]) -> (Self, usize) {
-        if s[0] == '[' {
-            let mut ofs = 1;
-            let mut list = Vec::new();
-            while ofs < s.len() && s[ofs] != ']' {
-                if s[ofs] == ',' {
-                    ofs += 1;
-                }
-                if ofs < s.len() {
-                    let ret = Self::parse(&s[ofs..]);
-                    list.push(ret.0);
-                    ofs += ret.1;
-                }
-            }
-            (Self::List(list), ofs + 1)
+    /// Parses `line`, a slice of `input`, which has to hold exactly one value.
+    fn parse_line(input: &str, line: &str) -> Result<Self, ParseError> {
+        let (value, rest) = Self::parse(input, line)?;
+        if rest.is_empty() {
+            Ok(value)
         } else {
-            let ofs = s
-                .iter()
-                .position(|c| *c == ']' || *c == ',')
-                .unwrap_or(s.len());
-            let s: String = s[0..ofs].iter().collect();
-            (Self::Integer(s.parse().unwrap()), ofs)
+            Err(ParseError::at(input, rest, "end of line"))
         }
     }
 
-    fn to_string(&self) -> String {
-        match self {
-            Value::Integer(v) => v.to_string(),
-            Value::List(l) => {
-                let mut s = String::from("[");
-                for i in 0..l.len() {
-                    if i > 0 {
-                        s += ",";
-                    }
-                    s += &l[i].to_string();
+    /// Parses a value from the start of `s`, returning it along with the rest of `s`.
+    fn parse<'a>(input: &str, s: &'a str) -> Result<(Self, &'a str), ParseError> {
+        if let Some(mut rest) = s.strip_prefix('[') {
+            let mut list = Vec::new();
+            if let Some(rest) = rest.strip_prefix(']') {
+                return Ok((Self::List(list), rest));
+            }
+            loop {
+                let (value, after) = Self::parse(input, rest)?;
+                list.push(value);
+                if let Some(after) = after.strip_prefix(',') {
+                    rest = after;
+                } else if let Some(after) = after.strip_prefix(']') {
+                    return Ok((Self::List(list), after));
+                } else {
+                    return Err(ParseError::at(input, after, "',' or ']'"));
                 }
-                s += "]";
-                s
             }
+        } else {
+            let len = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+            if len == 0 {
+                return Err(ParseError::at(input, s, "a number or '['"));
+            }
+            let (digits, rest) = s.split_at(len);
+            Ok((Self::Integer(parse::number(input, digits)?), rest))
         }
     }
 }
 
 impl PartialOrd for Value {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.compare(other))
+        Some(self.cmp(other))
     }
 }
 
@@ -91,36 +91,45 @@ impl Ord for Value {
     }
 }
 
-pub fn solve() {
-    let data = include_str!("inputs/13.txt");
-    let mut values: Vec<Value> = data
-        .split('\n')
-        .filter_map(|s| {
-            let chars: Vec<char> = s.trim().chars().collect();
-            if chars.is_empty() {
-                None
-            } else {
-                Some(Value::parse(&chars).0)
-            }
-        })
-        .collect();
+pub struct Day13;
 
-    let mut task1 = 0;
-    for i in 0..values.len() / 2 {
-        if values[2 * i] < values[2 * i + 1] {
-            task1 += i + 1;
-        }
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+    const INPUT: &'static str = include_str!("inputs/13.txt");
+
+    type Input = Vec<Value>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Value>, ParseError> {
+        input
+            .split('\n')
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .map(|s| Value::parse_line(input, s))
+            .collect()
     }
 
-    let div1 = Value::parse_str("[[2]]");
-    let div2 = Value::parse_str("[[6]]");
-    values.push(div1.clone());
-    values.push(div2.clone());
-    values.sort();
+    fn part1(values: &Vec<Value>) -> usize {
+        let mut task1 = 0;
+        for i in 0..values.len() / 2 {
+            if values[2 * i] < values[2 * i + 1] {
+                task1 += i + 1;
+            }
+        }
+        task1
+    }
 
-    let idx1 = values.iter().position(|v| *v == div1).unwrap();
-    let idx2 = values.iter().position(|v| *v == div2).unwrap();
+    fn part2(values: &Vec<Value>) -> usize {
+        let div1 = Value::parse_str("[[2]]");
+        let div2 = Value::parse_str("[[6]]");
+        let mut values = values.clone();
+        values.push(div1.clone());
+        values.push(div2.clone());
+        values.sort();
 
-    println!("[day 13] task 1: {}", task1);
-    println!("[day 13] task 2: {}", (idx1 + 1) * (idx2 + 1));
+        let idx1 = values.iter().position(|v| *v == div1).unwrap();
+        let idx2 = values.iter().position(|v| *v == div2).unwrap();
+        (idx1 + 1) * (idx2 + 1)
+    }
 }
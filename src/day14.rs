@@ -1,112 +1,138 @@
-use crate::{array2d::Array2D, vec2::Vec2i32};
-
-pub fn solve() {
-    let data = include_str!("inputs/14.txt");
-
-    let lines: Vec<Vec<Vec2i32>> = data
-        .split("\n")
-        .map(|l| l.trim())
-        .filter(|l| !l.is_empty())
-        .map(|l| {
-            l.split(" -> ")
-                .map(|part| {
-                    let mut parts = part.split(',');
-                    let x = parts.next().unwrap().parse().unwrap();
-                    let y = parts.next().unwrap().parse().unwrap();
-                    Vec2i32::new(x, y)
-                })
-                .collect()
-        })
-        .collect();
-
-    let min_y = lines
-        .iter()
-        .flatten()
-        .min_by(|a, b| a.y.cmp(&b.y))
-        .unwrap()
-        .y;
-    let max_y = lines
-        .iter()
-        .flatten()
-        .min_by(|a, b| b.y.cmp(&a.y))
-        .unwrap()
-        .y;
-    let min_x = lines
-        .iter()
-        .flatten()
-        .min_by(|a, b| a.x.cmp(&b.x))
-        .unwrap()
-        .x;
-    let max_x = lines
-        .iter()
-        .flatten()
-        .min_by(|a, b| b.x.cmp(&a.x))
-        .unwrap()
-        .x;
-
-    let floor = max_y + 2;
-    let min_x = min_x - floor;
-    let max_x = max_x + floor;
-    let min_y = min_y.min(0);
-    let min = Vec2i32::new(min_x, min_y);
-
-    let mut grid = Array2D::new((max_x - min_x + 3) as usize, (max_y - min_y + 5) as usize);
-    grid.fill(false);
-
-    for line in &lines {
-        for i in 1..line.len() {
-            let from = line[i - 1];
-            let to = line[i];
-            let dir = (to - from).signum();
-            let mut v = from;
-            loop {
-                grid.set(v - min, true);
-                if v == to {
-                    break;
-                }
-                v += dir;
-            }
-        }
-    }
+use std::io;
 
-    for x in min_x..max_x {
-        grid.set(Vec2i32::new(x, floor) - min, true);
-    }
+use crate::{
+    image::Frames,
+    infinite_grid::InfiniteGrid,
+    parse::{self, ParseError},
+    rect::Rect,
+    solution::Solution,
+    vec2::Vec2i32,
+};
+
+#[derive(Clone)]
+pub struct Cave {
+    grid: InfiniteGrid<bool>,
+    max_y: i32,
+    floor: i32,
+}
+
+/// Pours sand until it falls into the abyss (or reaches the floor), or until the source is blocked.
+/// Draws the cave into `frames` after each unit of sand.
+fn pour_sand(cave: &Cave, until_abyss: bool, mut frames: Option<&mut Frames>) -> usize {
+    let Cave {
+        mut grid,
+        max_y,
+        floor,
+    } = cave.clone();
+    let source = Vec2i32::new(500, 0);
 
     let mut curr = 0;
-    let mut task1 = None;
-    let mut task2 = None;
-    while task1.is_none() || task2.is_none() {
-        let mut sand = Vec2i32::new(500, 0);
+    loop {
+        let mut sand = source;
 
         while sand.y < floor {
             let mut moved = false;
             for ofs in [(0, 1), (-1, 1), (1, 1)] {
-                if !grid.at(sand - min + ofs) {
-                    sand += ofs;
+                let next = sand + ofs;
+                if next.y < floor && !grid.at(next) {
+                    sand = next;
                     moved = true;
                     break;
                 }
             }
 
             if !moved {
-                grid.set(sand - min, true);
+                grid.set(sand, true);
                 break;
             }
         }
 
-        if sand.y >= max_y && task1.is_none() {
-            task1 = Some(curr);
+        if let Some(frames) = frames.as_mut() {
+            let colour = |blocked: &bool| if *blocked { [194, 178, 128] } else { [0, 0, 0] };
+            frames.write_ppm(&grid.to_array2d(), colour);
+        }
+
+        if until_abyss && sand.y >= max_y {
+            return curr;
         }
 
         curr += 1;
 
-        if *grid.at(Vec2i32::new(500, 0) - min) {
-            task2 = Some(curr);
-            break;
+        if *grid.at(source) {
+            return curr;
+        }
+    }
+}
+
+/// Parses a path of rock, whose segments have to be horizontal or vertical.
+fn parse_path(input: &str, line: &str) -> Result<Vec<Vec2i32>, ParseError> {
+    let mut path: Vec<Vec2i32> = Vec::new();
+    for part in line.split(" -> ") {
+        let [x, y] = parse::integers_n(input, part)?;
+        let point = Vec2i32::new(x, y);
+        if let Some(prev) = path.last() {
+            if prev.x != point.x && prev.y != point.y {
+                return Err(ParseError::at(
+                    input,
+                    part,
+                    "a point in line with the previous one",
+                ));
+            }
+        }
+        path.push(point);
+    }
+    Ok(path)
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+    const INPUT: &'static str = include_str!("inputs/14.txt");
+
+    type Input = Cave;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Cave, ParseError> {
+        let lines: Vec<Vec<Vec2i32>> = parse::lines(input)
+            .map(|l| parse_path(input, l))
+            .collect::<Result<_, _>>()?;
+        if lines.is_empty() {
+            return Err(ParseError::at(input, input, "a path of rock"));
+        }
+
+        let mut grid = InfiniteGrid::new();
+        for line in &lines {
+            for i in 1..line.len() {
+                for v in line[i - 1].line_to(line[i]) {
+                    grid.set(v, true);
+                }
+            }
         }
+
+        let rocks = Rect::from_points(lines.iter().flatten().copied()).unwrap();
+        let max_y = rocks.max.y;
+        let floor = max_y + 2;
+
+        Ok(Cave { grid, max_y, floor })
     }
 
-    println!("[day 14] task 1: {}", task1.unwrap());
-    println!("[day 14] task 2: {}", task2.unwrap());
+    fn part1(cave: &Cave) -> usize {
+        pour_sand(cave, true, None)
+    }
+
+    fn part2(cave: &Cave) -> usize {
+        pour_sand(cave, false, None)
+    }
+
+    fn export(cave: &Cave) -> io::Result<()> {
+        for (name, until_abyss) in [("day14-abyss", true), ("day14-floor", false)] {
+            if let Some(mut frames) = Frames::from_env(name)? {
+                pour_sand(cave, until_abyss, Some(&mut frames));
+                frames.finish()?;
+            }
+        }
+        Ok(())
+    }
 }
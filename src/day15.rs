@@ -1,122 +1,81 @@
-use crate::vec2::Vec2i32;
+use crate::{
+    interval_set::IntervalSet,
+    parse::{self, ParseError},
+    solution::Solution,
+    vec2::Vec2i32,
+};
 
-struct Sensor {
+pub struct Sensor {
     sensor: Vec2i32,
     radius: i32,
 }
 
-struct Spans(Vec<(i32, i32)>);
-
-impl Spans {
-    fn add(&mut self, from: i32, to: i32) {
-        assert!(to > from);
-        self.0.push((from, to));
-        self.0.sort_by(|a, b| {
-            if a.0 == b.0 {
-                a.1.cmp(&b.1)
-            } else {
-                a.0.cmp(&b.0)
-            }
-        });
+/// The positions in row `y` that are within reach of a sensor.
+fn row_coverage(sensors: &[Sensor], y: i32) -> IntervalSet<i32> {
+    sensors
+        .iter()
+        .filter_map(|sensor| {
+            let reach = sensor.radius - (sensor.sensor.y - y).abs();
+            (reach >= 0).then(|| sensor.sensor.x - reach..sensor.sensor.x + reach + 1)
+        })
+        .collect()
+}
 
-        let mut i = 0;
-        while i + 1 < self.0.len() {
-            if self.0[i].1 >= self.0[i + 1].0 {
-                self.0[i].1 = self.0[i].1.max(self.0[i + 1].1);
-                self.0.remove(i + 1);
-            } else {
-                i += 1;
-            }
+/// Counts the positions in row `y` where no beacon can be.
+pub fn covered_positions((sensors, beacons): &(Vec<Sensor>, Vec<Vec2i32>), y: i32) -> usize {
+    let mut covered = row_coverage(sensors, y);
+    for beacon in beacons {
+        if beacon.y == y {
+            covered.remove(beacon.x..beacon.x + 1);
         }
     }
+    covered.total_len().try_into().unwrap()
+}
 
-    fn contains(&self, num: i32) -> bool {
-        self.0.iter().any(|span| span.0 <= num && span.1 > num)
-    }
-
-    fn clear(&mut self) {
-        self.0.clear();
-    }
+/// Finds the only position with both coordinates in `0..=max` that no sensor reaches and
+/// returns its tuning frequency, `x * 4000000 + y`.
+pub fn tuning_frequency((sensors, _): &(Vec<Sensor>, Vec<Vec2i32>), max: i32) -> Option<i64> {
+    (0..=max).find_map(|y| {
+        let free = row_coverage(sensors, y).complement(0..max + 1);
+        let gap = free.ranges().first()?;
+        Some(gap.start as i64 * 4000000 + y as i64)
+    })
 }
 
-pub fn solve() {
-    let data = include_str!("inputs/15.txt");
+pub struct Day15;
 
-    let mut beacons = Vec::new();
-    let sensors: Vec<Sensor> = data
-        .split("\n")
-        .filter(|line| !line.trim().is_empty())
-        .map(|line| {
-            let mut parts = line.trim().split('=');
-            parts.next();
-            let sx = parts.next().unwrap();
-            let sy = parts.next().unwrap();
-            let bx = parts.next().unwrap();
-            let by = parts.next().unwrap();
-            let sx = sx.split(",").next().unwrap();
-            let sy = sy.split(":").next().unwrap();
-            let bx = bx.split(",").next().unwrap();
-            let sx = sx.parse().unwrap();
-            let sy = sy.parse().unwrap();
-            let bx = bx.parse().unwrap();
-            let by = by.parse().unwrap();
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+    const INPUT: &'static str = include_str!("inputs/15.txt");
 
-            let sensor = Vec2i32::new(sx, sy);
-            let beacon = Vec2i32::new(bx, by);
-            let dist = (beacon - sensor).abs();
-            let radius = dist.x + dist.y;
-            if !beacons.contains(&beacon) {
-                beacons.push(beacon);
-            }
+    type Input = (Vec<Sensor>, Vec<Vec2i32>);
+    type Answer1 = usize;
+    type Answer2 = Option<i64>;
 
-            Sensor { sensor, radius }
-        })
-        .collect();
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut beacons = Vec::new();
+        let sensors: Vec<Sensor> = parse::lines(input)
+            .map(|line| {
+                let [sx, sy, bx, by] = parse::integers_n(input, line)?;
 
-    let mut spans = Spans(vec![]);
-    {
-        let y = 2000000;
+                let sensor = Vec2i32::new(sx, sy);
+                let beacon = Vec2i32::new(bx, by);
+                let radius = sensor.manhattan(beacon);
+                if !beacons.contains(&beacon) {
+                    beacons.push(beacon);
+                }
 
-        for sensor in &sensors {
-            let reach = sensor.radius - (sensor.sensor.y - y).abs();
-            if reach >= 0 {
-                spans.add(sensor.sensor.x - reach, sensor.sensor.x + reach + 1);
-            }
-        }
+                Ok(Sensor { sensor, radius })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok((sensors, beacons))
+    }
 
-        let mut task1 = spans.0.iter().map(|span| span.1 - span.0).sum::<i32>();
-        for beacon in beacons {
-            if beacon.y == y && spans.contains(beacon.x) {
-                task1 -= 1;
-            }
-        }
-        println!("[day 15] task 1: {}", task1);
+    fn part1(input: &Self::Input) -> usize {
+        covered_positions(input, 2000000)
     }
 
-    let sz = 4000000;
-    // let mut task2 = (0, 0);
-    // {
-    //     for y in 0..sz + 1 {
-    //         spans.clear();
-    //         for sensor in &sensors {
-    //             let reach = sensor.radius - (sensor.sensor.y - y).abs();
-    //             if reach >= 0 {
-    //                 spans.add(sensor.sensor.x - reach, sensor.sensor.x + reach + 1);
-    //             }
-    //         }
-    //         if spans.0.len() > 1 {
-    //             task2 = (spans.0[0].1, y);
-    //             break;
-    //         } else if spans.0.first().unwrap().0 > 0 {
-    //             task2 = (0, y);
-    //             break;
-    //         } else if spans.0.last().unwrap().1 < sz {
-    //             task2 = (sz, y);
-    //         }
-    //     }
-    // }
-    // println!(
-    //     "[day 15] task 2: {}",
-    //     task2.0 as i64 * sz as i64 + task2.1 as i64
-    // );
+    fn part2(input: &Self::Input) -> Option<i64> {
+        tuning_frequency(input, 4000000)
+    }
 }
@@ -1,7 +1,11 @@
-use std::{collections::HashMap, hash::Hash};
+use std::collections::HashMap;
+
+use crate::{
+    parse::{self, ParseError},
+    solution::Solution,
+};
 
 struct InputValve {
-    id: String,
     rate: i32,
     out: Vec<String>,
 }
@@ -25,7 +29,7 @@ fn get_connections(
     }
 }
 
-struct MergedValve {
+pub struct MergedValve {
     id: String,
     rate: i32,
     out: Vec<(i32, usize)>,
@@ -60,159 +64,136 @@ fn find_best_path(
     max_released
 }
 
-#[derive(Clone)]
-struct Actor {
-    target_valve: usize,
-    steps_remaining: i32,
-}
-
-fn find_best_path2(
+/// Records for every set of opened valves the most pressure that opening them can release.
+fn find_best_per_set(
     valves: &[MergedValve],
-    mut actors: [Actor; 2],
-    curr_actor: usize,
+    curr_valve: usize,
     visited_mask: usize,
     time_left: i32,
     curr_released: i32,
-) -> i32 {
-    let mut max_released = curr_released;
+    best: &mut HashMap<usize, i32>,
+) {
+    let entry = best.entry(visited_mask).or_insert(0);
+    *entry = (*entry).max(curr_released);
 
-    if time_left <= 0 {
-        return max_released;
-    }
+    for out in &valves[curr_valve].out {
+        if time_left < out.0 {
+            continue;
+        }
 
-    if curr_actor == 0 {
-        if actors[0].steps_remaining <= 0 && actors[1].steps_remaining <= 0 {
-            return max_released;
+        if (visited_mask & (1 << out.1)) != 0 {
+            continue;
         }
 
-        actors[0].steps_remaining -= 1;
-        actors[1].steps_remaining -= 1;
+        let next_valve = &valves[out.1];
+        let visited_mask = visited_mask | (1 << out.1);
+        let time_left = time_left - out.0;
+        let curr_released = curr_released + next_valve.rate * time_left;
+        find_best_per_set(valves, out.1, visited_mask, time_left, curr_released, best);
     }
+}
 
-    let next_actor = 1 - curr_actor;
-    let next_time_left = if curr_actor == 0 {
-        time_left
-    } else {
-        time_left - 1
-    };
-
-    // arrived at dest? -> pick next target
-    let mut checked_sub_paths = false;
-    if actors[curr_actor].steps_remaining == 0 {
-        let valve = &valves[actors[curr_actor].target_valve];
-        let curr_released = curr_released + valve.rate * time_left;
-
-        for out in &valve.out {
-            if next_time_left < out.0 {
-                continue;
-            }
+pub struct Valves {
+    valves: Vec<MergedValve>,
+    start_index: usize,
+}
 
-            if (visited_mask & (1 << out.1)) != 0 {
-                continue;
-            }
+pub struct Day16;
 
-            checked_sub_paths = true;
-
-            let mut actors = actors.clone();
-            actors[curr_actor].target_valve = out.1;
-            actors[curr_actor].steps_remaining = out.0;
-
-            let visited_mask = visited_mask | (1 << out.1);
-            let released = find_best_path2(
-                valves,
-                actors,
-                next_actor,
-                visited_mask,
-                next_time_left,
-                curr_released,
-            );
-            max_released = max_released.max(released);
-        }
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+    const INPUT: &'static str = include_str!("inputs/16.txt");
 
-        if !checked_sub_paths {
-            let released = find_best_path2(
-                valves,
-                actors,
-                next_actor,
-                visited_mask,
-                next_time_left,
-                curr_released,
-            );
-            max_released = max_released.max(released);
-        }
-    } else {
-        let released = find_best_path2(
-            valves,
-            actors,
-            next_actor,
-            visited_mask,
-            next_time_left,
-            curr_released,
-        );
-        max_released = max_released.max(released);
-    }
+    type Input = Valves;
+    type Answer1 = i32;
+    type Answer2 = i32;
 
-    max_released
-}
+    fn parse(input: &str) -> Result<Valves, ParseError> {
+        // 1. read input
+        let mut input_valves = HashMap::new();
+        let mut references = Vec::new();
+        for line in parse::lines(input) {
+            let [id, rate, tunnels] = parse::pattern(input, line, "Valve {} has flow rate={}; {}")?;
+            let rate = parse::number(input, rate)?;
 
-pub fn solve() {
-    let data = include_str!("inputs/16.txt");
+            let [valves] = parse::pattern(input, tunnels, "tunnels lead to valves {}")
+                .or_else(|_| parse::pattern(input, tunnels, "tunnel leads to valve {}"))?;
+            references.extend(valves.split(", "));
+            let out = valves.split(", ").map(|s| s.to_string()).collect();
 
-    // 1. read input
-    let mut input_valves = HashMap::new();
-    for line in data.split('\n') {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
+            let id = id.to_string();
+            input_valves.insert(id.clone(), InputValve { rate, out });
+        }
+
+        if let Some(unknown) = references.iter().find(|r| !input_valves.contains_key(**r)) {
+            return Err(ParseError::at(input, unknown, "a known valve"));
+        }
+        if !input_valves.contains_key("AA") {
+            return Err(ParseError::after(input, input.trim_end(), "a valve AA"));
         }
 
-        let id = line[6..8].to_string();
+        // 2. eliminate null valves
+        let mut merged_index = HashMap::new();
+        let mut merged_valves = Vec::new();
+        for input in &input_valves {
+            if input.0 == "AA" || input.1.rate > 0 {
+                merged_index.insert(input.0, merged_valves.len());
+                merged_valves.push(MergedValve {
+                    id: input.0.clone(),
+                    rate: input.1.rate,
+                    out: Vec::new(),
+                })
+            }
+        }
 
-        let semicolon = line.chars().position(|c| c == ';').unwrap();
-        let rate = line[23..semicolon].parse().unwrap();
+        // 3. find closest paths between any valves
+        for valve in &mut merged_valves {
+            let mut connections = HashMap::new();
+            get_connections(&input_valves, &valve.id, 0, &mut connections);
 
-        let valve = line.find("valve").unwrap();
-        let valves = line[valve + 6..].trim();
-        let out = valves.split(", ").map(|s| s.to_string()).collect();
+            for conn in &connections {
+                if valve.id != *conn.0 && input_valves[conn.0].rate > 0 {
+                    if let Some(idx) = merged_index.get(conn.0) {
+                        valve.out.push((*conn.1 + 1, *idx));
+                    }
+                }
+            }
+        }
 
-        input_valves.insert(id.clone(), InputValve { id, rate, out });
+        let start_index = *merged_index.get(&String::from("AA")).unwrap();
+
+        Ok(Valves {
+            valves: merged_valves,
+            start_index,
+        })
     }
 
-    // 2. eliminate null valves
-    let mut merged_index = HashMap::new();
-    let mut merged_valves = Vec::new();
-    for input in &input_valves {
-        if input.0 == "AA" || input.1.rate > 0 {
-            merged_index.insert(input.0, merged_valves.len());
-            merged_valves.push(MergedValve {
-                id: input.0.clone(),
-                rate: input.1.rate,
-                out: Vec::new(),
-            })
-        }
+    fn part1(input: &Valves) -> i32 {
+        let start_mask = 1 << input.start_index;
+        find_best_path(&input.valves, input.start_index, start_mask, 30, 0)
     }
 
-    // 3. find closest paths between any valves
-    for valve in &mut merged_valves {
-        let mut connections = HashMap::new();
-        get_connections(&input_valves, &valve.id, 0, &mut connections);
+    fn part2(input: &Valves) -> i32 {
+        // You and the elephant open disjoint sets of valves, so pair up the best of each set.
+        let start_mask = 1 << input.start_index;
+        let mut best = HashMap::new();
+        find_best_per_set(
+            &input.valves,
+            input.start_index,
+            start_mask,
+            26,
+            0,
+            &mut best,
+        );
 
-        for conn in &connections {
-            if valve.id != *conn.0 && input_valves[conn.0].rate > 0 {
-                if let Some(idx) = merged_index.get(conn.0) {
-                    valve.out.push((*conn.1 + 1, *idx));
+        let mut max_released = 0;
+        for (mine, released) in &best {
+            for (elephants, elephant_released) in &best {
+                if mine & elephants == start_mask {
+                    max_released = max_released.max(released + elephant_released);
                 }
             }
         }
+        max_released
     }
-
-    let start_index = *merged_index.get(&String::from("AA")).unwrap();
-    let start_mask = 1 << start_index;
-    let task1 = find_best_path(&merged_valves, start_index, start_mask, 30, 0);
-    println!("[day 16] task 1: {}", task1);
-
-    // let start = Actor { target_valve: start_index, steps_remaining: 1 };
-    // let actors = [start.clone(), start.clone()];
-    // let task2 = find_best_path2(&merged_valves, actors, 0, start_mask, 26, 0);
-    // println!("[day 16] task 2: {}", task2);
 }
@@ -1,83 +1,91 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fs, io};
 
 use crate::{
     array2d::Array2D,
+    bitgrid::BitGrid,
+    image::Frames,
+    parse::ParseError,
+    snapshot,
+    solution::Solution,
     vec2::{vec2, Vec2i32},
 };
 
 struct Rock {
-    coords: Vec<Vec2i32>,
-    grid: Array2D<bool>,
+    grid: BitGrid,
     w: i32,
     h: i32,
 }
 
 impl Rock {
-    fn from(coords: &[(i32, i32)]) -> Self {
-        let coords: Vec<Vec2i32> = coords.iter().map(|c| Vec2i32::new(c.0, c.1)).collect();
-        let w = 1 + coords.iter().map(|c| c.x).max().unwrap();
-        let h = 1 + coords.iter().map(|c| c.y).max().unwrap();
-        let mut grid = Array2D::new(w as usize, h as usize);
-        grid.fill(false);
-
-        for coord in &coords {
-            grid.set(*coord, true);
+    /// Creates a rock from its shape as drawn in the puzzle, with the bottom row last.
+    fn from_shape(shape: &str) -> Self {
+        let (cells, markers) = Array2D::from_str_map(shape, shape, "#", |ch| match ch {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap();
+        let (w, h) = (cells.width(), cells.height());
+        let mut grid = BitGrid::new(w, h);
+        for cell in markers.all('#') {
+            grid.set((cell.x as usize, h - 1 - cell.y as usize), true);
+        }
+        Self {
+            grid,
+            w: w as i32,
+            h: h as i32,
         }
-
-        Self { coords, grid, w, h }
     }
 }
 
-fn can_move(board: &Array2D<bool>, rock: &Rock, pos: Vec2i32) -> bool {
-    if pos.x < 0 || pos.x + rock.w > 7 {
-        false
-    } else {
-        assert!(pos.y >= 0);
-        !rock.coords.iter().any(|coord| *board.at(pos + *coord))
+fn can_move(board: &BitGrid, rock: &Rock, pos: Vec2i32) -> bool {
+    if pos.x < 0 || pos.y < 0 || pos.x + rock.w > 7 || pos.y + rock.h > board.height() as i32 {
+        return false;
     }
+    (0..rock.h as usize).all(|y| board.row(pos.y as usize + y) & (rock.grid.row(y) << pos.x) == 0)
 }
 
-fn print_board(board: &Array2D<bool>, max_y: i32, rock: &Rock, pos: Vec2i32) {
-    for y in (0..max_y).rev() {
-        let mut s = String::new();
+/// Draws the lowest `max_y` rows of the board upside down, so that the floor is at the bottom:
+/// 0 for air, 1 for settled rock and 2 for the rock at `pos`.
+fn board_image(board: &BitGrid, max_y: i32, rock: &Rock, pos: Vec2i32) -> Array2D<u8> {
+    let mut image = Array2D::new(7, max_y as usize);
+    for y in 0..max_y {
         for x in 0..7 {
-            s += if *board.at((x, y)) {
-                "#"
-            } else if x >= pos.x
+            let value = if x >= pos.x
                 && y >= pos.y
                 && x - pos.x < rock.w
                 && y - pos.y < rock.h
-                && *rock.grid.at(vec2(x, y) - pos)
+                && rock.grid.at(vec2(x, y) - pos)
             {
-                "@"
+                2
             } else {
-                "."
+                board.at((x, y)) as u8
             };
+            image.set((x, max_y - 1 - y), value);
         }
-        println!("|{}|", s);
     }
-    println!("+-------+");
-    println!("");
+    image
 }
 
-fn board_height(board: &Array2D<bool>) -> usize {
+fn board_height(board: &BitGrid) -> usize {
     let mut height = 0;
     for y in 0..board.height() {
-        if board.slice(y).iter().any(|b| *b) {
+        if board.row(y) != 0 {
             height = y;
         } else {
             break;
         }
     }
-    return height + 1;
+    height + 1
 }
 
 fn board_simulate(
-    board: &mut Array2D<bool>,
+    board: &mut BitGrid,
     rocks: &[Rock],
     jets: &[i32],
     start_step: usize,
     rock_count: usize,
+    mut frames: Option<&mut Frames>,
 ) -> usize {
     let mut spawn = board_height(board) as i32 + 3;
     let mut step = start_step;
@@ -90,17 +98,21 @@ fn board_simulate(
             let jet = jets[step % jets.len()];
             step += 1;
 
-            if can_move(&board, rock, pos + (jet, 0)) {
+            if can_move(board, rock, pos + (jet, 0)) {
                 pos += (jet, 0);
             }
 
-            if can_move(&board, rock, pos + (0, -1)) {
+            if can_move(board, rock, pos + (0, -1)) {
                 pos += (0, -1);
             } else {
-                for c in &rock.coords {
-                    board.set(pos + *c, true);
+                for y in 0..rock.h {
+                    board.or_row((pos.y + y) as usize, rock.grid.row(y as usize) << pos.x);
                 }
                 spawn = spawn.max(pos.y + rock.h + 3);
+                if let Some(frames) = frames.as_mut() {
+                    let image = board_image(board, spawn, rock, pos);
+                    frames.write_pgm(&image, |v| [0, 160, 255][*v as usize]);
+                }
                 break;
             }
         }
@@ -112,22 +124,15 @@ fn board_simulate(
 #[derive(Clone)]
 struct BoardLayout {
     step: usize,
-    top_rows: Array2D<bool>,
+    top_rows: BitGrid,
     hash: String,
 }
 
 impl BoardLayout {
-    fn new(board: &Array2D<bool>, step: usize) -> Self {
+    fn new(board: &BitGrid, step: usize) -> Self {
         let height = board_height(board);
-        let mut top_rows = Array2D::new(7, height);
-        for y in 0..height {
-            let slice = board.slice(y);
-            top_rows.slice_mut(y).clone_from_slice(slice);
-        }
-        let mut hash: String = top_rows
-            .iter()
-            .map(|b| if *b { '1' } else { '0' })
-            .collect();
+        let top_rows = board.crop_rows(0..height);
+        let mut hash = top_rows.render('1', '0');
         hash += &format!("{}", step);
 
         Self {
@@ -140,7 +145,6 @@ impl BoardLayout {
 
 #[derive(Clone)]
 struct BoardDropping {
-    start_layout: BoardLayout,
     end_layout: BoardLayout,
     height_diff: usize,
     loop_height_diff: Option<usize>,
@@ -149,36 +153,26 @@ struct BoardDropping {
 
 impl BoardDropping {
     fn new(start_layout: BoardLayout, rocks: &[Rock], jets: &[i32], rock_count: usize) -> Self {
-        let mut board = Array2D::new(7, rock_count * 20 + start_layout.top_rows.height());
-        board.fill(false);
+        let mut board = BitGrid::new(7, rock_count * 20 + start_layout.top_rows.height());
 
         // fill board
         for y in 0..start_layout.top_rows.height() {
-            board
-                .slice_mut(y)
-                .clone_from_slice(start_layout.top_rows.slice(y));
+            board.set_row(y, start_layout.top_rows.row(y));
         }
 
         let old_height = board_height(&board);
-        let new_step = board_simulate(&mut board, rocks, jets, start_layout.step, rock_count);
+        let new_step = board_simulate(&mut board, rocks, jets, start_layout.step, rock_count, None);
         let new_height = board_height(&board);
 
         let height_diff = new_height - old_height;
 
         // chop of bottom parts of the board
         let max_height = new_height.min(50);
-        let mut new_board = Array2D::new(7, max_height);
-        new_board.fill(false);
-        for y in 0..max_height {
-            new_board
-                .slice_mut(y)
-                .clone_from_slice(board.slice(new_height - max_height + y));
-        }
+        let new_board = board.crop_rows(new_height - max_height..new_height);
 
         let end_layout = BoardLayout::new(&new_board, new_step);
 
         Self {
-            start_layout,
             end_layout,
             height_diff,
             loop_height_diff: None,
@@ -187,35 +181,59 @@ impl BoardDropping {
     }
 }
 
-pub fn solve() {
-    let data = include_str!("inputs/17.txt");
-    let jets: Vec<i32> = data
-        .chars()
-        .filter_map(|c| match c {
-            '<' => Some(-1),
-            '>' => Some(1),
-            _ => None,
-        })
-        .collect();
-
-    let rocks = vec![
-        Rock::from(&[(0, 0), (1, 0), (2, 0), (3, 0)]),
-        Rock::from(&[(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)]),
-        Rock::from(&[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)]),
-        Rock::from(&[(0, 0), (0, 1), (0, 2), (0, 3)]),
-        Rock::from(&[(0, 0), (0, 1), (1, 0), (1, 1)]),
-    ];
-
-    {
-        let mut board = Array2D::new(7, 16000);
-        board.fill(false);
-        board.slice_mut(0).clone_from_slice(&[true; 7]);
-        board_simulate(&mut board, &rocks, &jets, 0, 2022);
-        println!("[day 17] task 1: {}", board_height(&board) - 1);
+fn rocks() -> Vec<Rock> {
+    vec![
+        Rock::from_shape("####"),
+        Rock::from_shape(".#.\n###\n.#."),
+        Rock::from_shape("..#\n..#\n###"),
+        Rock::from_shape("#\n#\n#\n#"),
+        Rock::from_shape("##\n##"),
+    ]
+}
+
+/// Drops the 2022 rocks of part 1 and returns the board along with the number of rows in use,
+/// including the floor.
+fn tower(jets: &[i32], frames: Option<&mut Frames>) -> (BitGrid, usize) {
+    let rocks = rocks();
+    let mut board = BitGrid::new(7, 16000);
+    board.set_row(0, 0b111_1111);
+    board_simulate(&mut board, &rocks, jets, 0, 2022, frames);
+    let height = board_height(&board);
+    (board, height)
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u32 = 17;
+    const INPUT: &'static str = include_str!("inputs/17.txt");
+
+    type Input = Vec<i32>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+        let line = input.trim();
+        if line.is_empty() {
+            return Err(ParseError::at(input, line, "a jet pattern"));
+        }
+        line.char_indices()
+            .map(|(i, c)| match c {
+                '<' => Ok(-1),
+                '>' => Ok(1),
+                _ => Err(ParseError::at(input, &line[i..], "'<' or '>'")),
+            })
+            .collect()
+    }
+
+    fn part1(jets: &Vec<i32>) -> usize {
+        let (_, height) = tower(jets, None);
+        height - 1
     }
 
-    {
-        let mut initial_board = Array2D::new(7, 1);
+    fn part2(jets: &Vec<i32>) -> usize {
+        let rocks = rocks();
+        let mut initial_board = BitGrid::new(7, 1);
         initial_board.fill(true);
         let initial_layout = BoardLayout::new(&initial_board, 0);
 
@@ -226,7 +244,7 @@ pub fn solve() {
         let mut current_blocks = 0;
         let mut layout = initial_layout.clone();
         loop {
-            let dropping = BoardDropping::new(layout.clone(), &rocks, &jets, rocks.len());
+            let dropping = BoardDropping::new(layout.clone(), &rocks, jets, rocks.len());
             current_height += dropping.height_diff;
             current_blocks += rocks.len();
             if droppings.contains_key(&layout.hash) {
@@ -263,8 +281,28 @@ pub fn solve() {
             height += iterations * loop_diff;
         }
 
-        let last_dropping = BoardDropping::new(curr_layout, &rocks, &jets, rocks_left);
+        let last_dropping = BoardDropping::new(curr_layout, &rocks, jets, rocks_left);
         height += last_dropping.height_diff;
-        println!("[day 17] task 2: {}", height);
+        height
+    }
+
+    fn export(jets: &Vec<i32>) -> io::Result<()> {
+        let mut frames = Frames::from_env("day17")?;
+        let snapshots = snapshot::env_dir();
+        if frames.is_none() && snapshots.is_none() {
+            return Ok(());
+        }
+
+        let (board, height) = tower(jets, frames.as_mut());
+        if let Some(frames) = frames {
+            frames.finish()?;
+        }
+        if let Some(dir) = snapshots {
+            fs::create_dir_all(&dir)?;
+            board
+                .crop_rows(0..height)
+                .save_snapshot(&dir.join("day17.bin"))?;
+        }
+        Ok(())
     }
 }
@@ -1,5 +1,11 @@
+use std::io;
+
 use crate::{
     array3d::Array3D,
+    cuboid::Cuboid,
+    mesh::{self, Mesh},
+    parse::{self, ParseError},
+    solution::Solution,
     vec3::{vec3, Vec3i32},
 };
 
@@ -10,166 +16,99 @@ enum Cell {
     Steam,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for Cell {
     fn default() -> Self {
         Self::Air
     }
 }
 
-fn flood_fill_steam(grid: &mut Array3D<Cell>, pos: Vec3i32) {
-    let mut cells = vec![pos];
-    let mut idx = 0;
-
-    while idx < cells.len() {
-        let pos = cells[idx];
-        let cell = *grid.at(pos);
-        idx += 1;
+/// Builds a grid around `cubes` with a layer of air on every side, and returns it along with the
+/// position of its first cell.
+fn build_grid(cubes: &[Vec3i32]) -> (Array3D<Cell>, Vec3i32) {
+    let bounds = Cuboid::from_points(cubes.iter().copied())
+        .expect("no cubes")
+        .expand(1);
+    let mut grid = Array3D::new_covering(&bounds);
 
-        if cell != Cell::Air {
-            continue;
-        }
-        grid.set(pos, Cell::Steam);
+    for cube in cubes {
+        grid.set(*cube - bounds.min, Cell::Rock);
+    }
 
-        let x0 = (pos.x - 1).max(0);
-        let y0 = (pos.y - 1).max(0);
-        let z0 = (pos.z - 1).max(0);
-        let x1 = (pos.x + 2).min(grid.width() as i32);
-        let y1 = (pos.y + 2).min(grid.height() as i32);
-        let z1 = (pos.z + 2).min(grid.depth() as i32);
+    (grid, bounds.min)
+}
 
-        for x in x0..x1 {
-            if x != pos.x && *grid.at((x, pos.y, pos.z)) == Cell::Air {
-                cells.push(vec3(x, pos.y, pos.z));
-            }
-        }
-        for y in y0..y1 {
-            if y != pos.y && *grid.at((pos.x, y, pos.z)) == Cell::Air {
-                cells.push(vec3(pos.x, y, pos.z));
-            }
-        }
-        for z in z0..z1 {
-            if z != pos.z && *grid.at((pos.x, pos.y, z)) == Cell::Air {
-                cells.push(vec3(pos.x, pos.y, z));
-            }
-        }
-    }
+/// Builds the grid of `build_grid` with steam filling the air outside the droplet.
+fn steam_grid(cubes: &[Vec3i32]) -> (Array3D<Cell>, Vec3i32) {
+    let (mut grid, min) = build_grid(cubes);
+    grid.flood_fill(vec3(0, 0, 0), |cell| *cell == Cell::Air, Cell::Steam);
+    (grid, min)
 }
 
-pub fn solve() {
-    let data = include_str!("inputs/18.txt");
-    let cubes: Vec<Vec3i32> = data
-        .split("\n")
-        .filter_map(|line| {
-            let line = line.trim();
-            if line.is_empty() {
-                None
-            } else {
-                let mut parts = line.split(',');
-                let x = parts.next().unwrap().parse().unwrap();
-                let y = parts.next().unwrap().parse().unwrap();
-                let z = parts.next().unwrap().parse().unwrap();
-                Some(vec3(x, y, z))
-            }
+/// Counts the sides of `cubes` that face a cell accepted by `exposed`.
+fn exposed_sides(
+    grid: &Array3D<Cell>,
+    cubes: &[Vec3i32],
+    min: Vec3i32,
+    exposed: impl Fn(Cell) -> bool,
+) -> i32 {
+    cubes
+        .iter()
+        .map(|cube| {
+            Vec3i32::NEIGHBORS6
+                .iter()
+                .filter(|ofs| exposed(*grid.at(*cube - min + **ofs)))
+                .count() as i32
         })
-        .collect();
+        .sum()
+}
 
-    let mut min = vec3(i32::MAX, i32::MAX, i32::MAX);
-    let mut max = vec3(i32::MIN, i32::MIN, i32::MIN);
-    for cube in &cubes {
-        min.x = min.x.min(cube.x);
-        min.y = min.y.min(cube.y);
-        min.z = min.z.min(cube.z);
-        max.x = max.x.max(cube.x);
-        max.y = max.y.max(cube.y);
-        max.z = max.z.max(cube.z);
-    }
-    min -= (1, 1, 1);
-    max += (2, 2, 2);
+pub struct Day18;
 
-    let sz = max - min;
-    let mut grid = Array3D::new(sz.x as usize, sz.y as usize, sz.z as usize);
+impl Solution for Day18 {
+    const DAY: u32 = 18;
+    const INPUT: &'static str = include_str!("inputs/18.txt");
 
-    for cube in &cubes {
-        grid.set(*cube - min, Cell::Rock);
+    type Input = Vec<Vec3i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<Vec3i32>, ParseError> {
+        let cubes: Vec<Vec3i32> = input
+            .split("\n")
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let (x, rest) = parse::split_once(input, line, ",")?;
+                let (y, z) = parse::split_once(input, rest, ",")?;
+                let x = parse::number(input, x)?;
+                let y = parse::number(input, y)?;
+                let z = parse::number(input, z)?;
+                Ok(vec3(x, y, z))
+            })
+            .collect::<Result<_, _>>()?;
+        if cubes.is_empty() {
+            return Err(ParseError::at(input, input, "a cube"));
+        }
+        Ok(cubes)
     }
 
-    let task1 = cubes
-        .iter()
-        .map(|cube| {
-            let mut sides = 0;
-            sides += if *grid.at(*cube + (-1, 0, 0) - min) == Cell::Rock {
-                0
-            } else {
-                1
-            };
-            sides += if *grid.at(*cube + (1, 0, 0) - min) == Cell::Rock {
-                0
-            } else {
-                1
-            };
-            sides += if *grid.at(*cube + (0, -1, 0) - min) == Cell::Rock {
-                0
-            } else {
-                1
-            };
-            sides += if *grid.at(*cube + (0, 1, 0) - min) == Cell::Rock {
-                0
-            } else {
-                1
-            };
-            sides += if *grid.at(*cube + (0, 0, -1) - min) == Cell::Rock {
-                0
-            } else {
-                1
-            };
-            sides += if *grid.at(*cube + (0, 0, 1) - min) == Cell::Rock {
-                0
-            } else {
-                1
-            };
-            sides
-        })
-        .sum::<i32>();
-    println!("[day 18] task 1: {}", task1);
+    fn part1(cubes: &Vec<Vec3i32>) -> i32 {
+        let (grid, min) = build_grid(cubes);
+        exposed_sides(&grid, cubes, min, |cell| cell != Cell::Rock)
+    }
 
-    flood_fill_steam(&mut grid, vec3(0, 0, 0));
+    fn part2(cubes: &Vec<Vec3i32>) -> i32 {
+        let (grid, min) = steam_grid(cubes);
+        exposed_sides(&grid, cubes, min, |cell| cell == Cell::Steam)
+    }
 
-    let task2 = cubes
-        .iter()
-        .map(|cube| {
-            let mut sides = 0;
-            sides += if *grid.at(*cube + (-1, 0, 0) - min) != Cell::Steam {
-                0
-            } else {
-                1
-            };
-            sides += if *grid.at(*cube + (1, 0, 0) - min) != Cell::Steam {
-                0
-            } else {
-                1
-            };
-            sides += if *grid.at(*cube + (0, -1, 0) - min) != Cell::Steam {
-                0
-            } else {
-                1
-            };
-            sides += if *grid.at(*cube + (0, 1, 0) - min) != Cell::Steam {
-                0
-            } else {
-                1
-            };
-            sides += if *grid.at(*cube + (0, 0, -1) - min) != Cell::Steam {
-                0
-            } else {
-                1
-            };
-            sides += if *grid.at(*cube + (0, 0, 1) - min) != Cell::Steam {
-                0
-            } else {
-                1
-            };
-            sides
-        })
-        .sum::<i32>();
-    println!("[day 18] task 2: {}", task2);
+    fn export(cubes: &Vec<Vec3i32>) -> io::Result<()> {
+        if let Some(dir) = mesh::env_dir() {
+            let (grid, _) = steam_grid(cubes);
+            let mesh = Mesh::greedy(&grid, |cell| *cell != Cell::Steam);
+            mesh.save(&dir, "day18")?;
+        }
+        Ok(())
+    }
 }
@@ -1,9 +1,11 @@
 use crate::{
+    parse::{self, ParseError},
+    solution::Solution,
     vec2::{vec2, Vec2u32},
     vec4::{vec4, Vec4u32},
 };
 
-struct Blueprint {
+pub struct Blueprint {
     id: u32,
     ore: u32,
     clay: u32,
@@ -34,10 +36,15 @@ impl State {
             robots: self.robots,
         }
     }
+    /// Pays `res` for the new `robots`, which only start collecting in the next minute.
     fn next_with_delta(&self, res: Vec4u32, robots: Vec4u32) -> Self {
+        let resources = self
+            .resources
+            .checked_sub(res)
+            .expect("can't afford the robot");
         Self {
             minutes_left: self.minutes_left - 1,
-            resources: self.resources + self.robots - res,
+            resources: resources + self.robots,
             robots: self.robots + robots,
         }
     }
@@ -56,32 +63,28 @@ impl State {
 }
 
 impl Blueprint {
-    fn parse(line: &str) -> Self {
-        let line: String = line
-            .trim()
-            .chars()
-            .filter_map(|c| if c != ':' { Some(c) } else { None })
-            .collect();
-        let parts: Vec<&str> = line.split(" ").collect();
-        let id = parts[1].parse().unwrap();
-        let ore: u32 = parts[6].parse().unwrap();
-        let clay = parts[12].parse().unwrap();
-        let obsidian1 = parts[18].parse().unwrap();
-        let obsidian2 = parts[21].parse().unwrap();
-        let geode1 = parts[27].parse().unwrap();
-        let geode2 = parts[30].parse().unwrap();
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        let fields = parse::pattern::<7>(
+            input,
+            line,
+            "Blueprint {}: Each ore robot costs {} ore. Each clay robot costs {} ore. \
+             Each obsidian robot costs {} ore and {} clay. \
+             Each geode robot costs {} ore and {} obsidian.",
+        )?;
+        let [id, ore, clay, obsidian1, obsidian2, geode1, geode2] =
+            parse::numbers::<u32, 7>(input, fields)?;
         let max_ore = ore.max(clay).max(obsidian1).max(geode1);
         let max_clay = obsidian2;
         let max_obsidian = geode2;
         let max_robots = vec4(max_ore, max_clay, max_obsidian, u32::MAX);
-        Self {
+        Ok(Self {
             id,
             ore,
             clay,
             obsidian: vec2(obsidian1, obsidian2),
             geode: vec2(geode1, geode2),
             max_robots,
-        }
+        })
     }
 
     fn find_best_result(&self, state: &State, curr_best: u32) -> u32 {
@@ -124,33 +127,43 @@ impl Blueprint {
         if has_obsidian {
             this_best = this_best
                 .max(self.find_best_result(&state.next_obsidian(self.obsidian), curr_best));
-            curr_best = curr_best.max(this_best);
         }
 
         this_best
     }
 }
 
-pub fn solve() {
-    let input = String::from(include_str!("inputs/19.txt"));
-    let blueprints: Vec<Blueprint> = input
-        .split("\n")
-        .filter(|l| !l.trim().is_empty())
-        .map(|l| Blueprint::parse(l))
-        .collect();
-
-    let task1: u32 = blueprints
-        .iter()
-        .map(|blueprint| {
-            let best = blueprint.find_best_result(&State::initial(24), 0);
-            blueprint.id * best
-        })
-        .sum();
-    println!("[day 19] task 1: {}", task1);
-
-    let task2: u32 = blueprints[0..3]
-        .iter()
-        .map(|blueprint| blueprint.find_best_result(&State::initial(32), 0))
-        .product();
-    println!("[day 19] task 2: {}", task2);
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u32 = 19;
+    const INPUT: &'static str = include_str!("inputs/19.txt");
+
+    type Input = Vec<Blueprint>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<Blueprint>, ParseError> {
+        parse::lines(input)
+            .map(|l| Blueprint::parse(input, l))
+            .collect()
+    }
+
+    fn part1(blueprints: &Vec<Blueprint>) -> u32 {
+        blueprints
+            .iter()
+            .map(|blueprint| {
+                let best = blueprint.find_best_result(&State::initial(24), 0);
+                blueprint.id * best
+            })
+            .sum()
+    }
+
+    fn part2(blueprints: &Vec<Blueprint>) -> u32 {
+        blueprints
+            .iter()
+            .take(3)
+            .map(|blueprint| blueprint.find_best_result(&State::initial(32), 0))
+            .product()
+    }
 }
@@ -1,3 +1,8 @@
+use crate::{
+    parse::{self, ParseError},
+    solution::Solution,
+};
+
 fn get_score(a: i32, b: i32) -> i32 {
     let diff = b - a;
     let score = match diff {
@@ -29,21 +34,43 @@ fn get_score_2(a: char, b: char) -> i32 {
     get_score(a, b)
 }
 
-pub fn solve() {
-    let lines = include_str!("inputs/2.txt").split('\n');
-
-    let mut score1 = 0;
-    let mut score2 = 0;
-    for line in lines {
-        if !line.is_empty() {
-            let mut parts = line.split(' ');
-            let a = parts.next().unwrap().chars().next().unwrap();
-            let b = parts.next().unwrap().chars().next().unwrap();
-            score1 += get_score_1(a, b);
-            score2 += get_score_2(a, b);
-        }
+/// Parses a token that is a single one of the characters in `shapes`.
+fn parse_shape(input: &str, token: &str, shapes: &str) -> Result<char, ParseError> {
+    let mut chars = token.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) if shapes.contains(ch) => Ok(ch),
+        _ => Err(ParseError::at(input, token, format!("one of {}", shapes))),
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+    const INPUT: &'static str = include_str!("inputs/2.txt");
+
+    type Input = Vec<(char, char)>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<(char, char)>, ParseError> {
+        input
+            .split('\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let mut parts = line.split(' ');
+                let a = parse::next(input, line, &mut parts, "a shape")?;
+                let b = parse::next(input, line, &mut parts, "a shape")?;
+                Ok((parse_shape(input, a, "ABC")?, parse_shape(input, b, "XYZ")?))
+            })
+            .collect()
     }
 
-    println!("[day 02] task 1: {}", score1);
-    println!("[day 02] task 2: {}", score2);
+    fn part1(rounds: &Vec<(char, char)>) -> i32 {
+        rounds.iter().map(|(a, b)| get_score_1(*a, *b)).sum()
+    }
+
+    fn part2(rounds: &Vec<(char, char)>) -> i32 {
+        rounds.iter().map(|(a, b)| get_score_2(*a, *b)).sum()
+    }
 }
@@ -1,4 +1,8 @@
-use crate::vec3::{vec3, Vec3i64};
+use crate::{
+    parse::{self, ParseError},
+    solution::Solution,
+    vec3::{vec3, Vec3i64},
+};
 
 struct Item {
     value: i64,
@@ -17,6 +21,7 @@ fn plus_n(items: &[Item], from: usize, n: usize) -> usize {
 
 fn reset(items: &mut [Item]) {
     let count = items.len();
+    #[allow(clippy::needless_range_loop)]
     for i in 0..items.len() {
         items[i].prev = (i + count - 1) % count;
         items[i].next = (i + 1) % count;
@@ -55,55 +60,62 @@ fn mix(items: &mut [Item]) {
 fn result(items: &[Item]) -> (i64, Vec3i64) {
     let null = items.iter().position(|item| item.value == 0).unwrap();
 
-    let plus1000 = plus_n(&items, null, 1000);
-    let plus2000 = plus_n(&items, null, 2000);
-    let plus3000 = plus_n(&items, null, 3000);
+    let plus1000 = plus_n(items, null, 1000);
+    let plus2000 = plus_n(items, null, 2000);
+    let plus3000 = plus_n(items, null, 3000);
     let a = items[plus1000].value;
     let b = items[plus2000].value;
     let c = items[plus3000].value;
     (a + b + c, vec3(a, b, c))
 }
 
-fn print(items: &[Item]) {
-    let mut curr = 0;
-    let mut s = String::new();
-    loop {
-        s += &items[curr].value.to_string();
-        s += ", ";
-        curr = items[curr].next;
-        if curr == 0 {
-            break;
-        }
-    }
-    println!("{}", s);
-}
-
-pub fn solve() {
-    let input = String::from(include_str!("inputs/20.txt"));
-    let mut items: Vec<Item> = input
-        .split("\n")
-        .filter_map(|line| {
-            line.trim().parse().ok().map(|value| Item {
-                value,
-                prev: 0,
-                next: 0,
-            })
+fn items(values: &[i64], key: i64) -> Vec<Item> {
+    let mut items: Vec<Item> = values
+        .iter()
+        .map(|value| Item {
+            value: value * key,
+            prev: 0,
+            next: 0,
         })
         .collect();
-
     reset(&mut items);
-    mix(&mut items);
-    let task1 = result(&items);
+    items
+}
 
-    for item in &mut items {
-        item.value *= 811589153;
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u32 = 20;
+    const INPUT: &'static str = include_str!("inputs/20.txt");
+
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+        let values = input
+            .split("\n")
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .map(|line| parse::number(input, line))
+            .collect::<Result<Vec<i64>, _>>()?;
+        if !values.contains(&0) {
+            return Err(ParseError::after(input, input.trim_end(), "a 0"));
+        }
+        Ok(values)
     }
-    reset(&mut items);
-    for _ in 0..10 {
+
+    fn part1(values: &Vec<i64>) -> i64 {
+        let mut items = items(values, 1);
         mix(&mut items);
+        result(&items).0
     }
-    let task2 = result(&items);
 
-    println!("[day 20] task 1: {:?}", task1);
-    println!("[day 20] task 2: {:?}", task2);
+    fn part2(values: &Vec<i64>) -> i64 {
+        let mut items = items(values, 811589153);
+        for _ in 0..10 {
+            mix(&mut items);
+        }
+        result(&items).0
+    }
 }
@@ -1,6 +1,12 @@
 use std::collections::HashMap;
 
-enum Operator {
+use crate::{
+    parse::{self, ParseError},
+    solution::Solution,
+};
+
+#[derive(Clone)]
+pub enum Operator {
     Plus,
     Minus,
     Mul,
@@ -8,36 +14,42 @@ enum Operator {
 }
 
 impl Operator {
-    fn from(s: &str) -> Self {
+    fn parse(input: &str, s: &str) -> Result<Self, ParseError> {
         match s {
-            "+" => Self::Plus,
-            "-" => Self::Minus,
-            "*" => Self::Mul,
-            "/" => Self::Div,
-            _ => panic!("nay"),
+            "+" => Ok(Self::Plus),
+            "-" => Ok(Self::Minus),
+            "*" => Ok(Self::Mul),
+            "/" => Ok(Self::Div),
+            _ => Err(ParseError::at(input, s, "'+', '-', '*' or '/'")),
         }
     }
 }
 
-enum Monkey {
+#[derive(Clone)]
+pub enum Monkey {
     Human,
     Number(i64),
     Operation(String, Operator, String),
 }
 
 impl Monkey {
-    fn from(s: &str) -> Self {
-        let s = s.trim();
-        let parts: Vec<&str> = s.split(" ").collect();
-        if parts.len() == 1 {
-            Self::Number(parts[0].parse().unwrap())
-        } else {
-            assert!(parts.len() == 3);
-            Self::Operation(
-                parts[0].to_string(),
-                Operator::from(parts[1]),
-                parts[2].to_string(),
-            )
+    /// Parses the job of a monkey, adding the names of the monkeys it depends on to `references`.
+    fn parse<'a>(
+        input: &str,
+        s: &'a str,
+        references: &mut Vec<&'a str>,
+    ) -> Result<Self, ParseError> {
+        match parse::pattern(input, s, "{} {} {}") {
+            Ok([left, op, right]) => {
+                references.push(left);
+                references.push(right);
+                Ok(Self::Operation(
+                    left.to_string(),
+                    Operator::parse(input, op)?,
+                    right.to_string(),
+                ))
+            }
+            Err(_) => Ok(Self::Number(parse::number(input, s)?)),
         }
     }
 }
@@ -59,6 +71,7 @@ fn get(monkeys: &HashMap<String, Monkey>, name: &str) -> Option<i64> {
     }
 }
 
+#[allow(clippy::unnecessary_unwrap)]
 fn get_human_number(monkeys: &HashMap<String, Monkey>, node: &str, expected: i64) -> i64 {
     let monkey = monkeys.get(node).unwrap();
     if let Monkey::Human = monkey {
@@ -70,8 +83,8 @@ fn get_human_number(monkeys: &HashMap<String, Monkey>, node: &str, expected: i64
         _ => panic!("nay"),
     };
 
-    let left_value = get(&monkeys, left);
-    let right_value = get(&monkeys, right);
+    let left_value = get(monkeys, left);
+    let right_value = get(monkeys, right);
     match op {
         Operator::Plus => {
             if left_value.is_some() {
@@ -104,34 +117,57 @@ fn get_human_number(monkeys: &HashMap<String, Monkey>, node: &str, expected: i64
     }
 }
 
-pub fn solve() {
-    let input = include_str!("inputs/21.txt");
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u32 = 21;
+    const INPUT: &'static str = include_str!("inputs/21.txt");
 
-    let mut monkeys = HashMap::new();
-    for line in input.split("\n") {
-        let line = line.trim();
-        if !line.is_empty() {
-            let name = line[0..4].to_string();
-            let monkey = Monkey::from(&line[6..]);
-            monkeys.insert(name, monkey);
+    type Input = HashMap<String, Monkey>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut monkeys = HashMap::new();
+        let mut references = Vec::new();
+        for line in parse::lines(input) {
+            let [name, job] = parse::pattern(input, line, "{}: {}")?;
+            let monkey = Monkey::parse(input, job, &mut references)?;
+            monkeys.insert(name.to_string(), monkey);
+        }
+
+        if let Some(unknown) = references.iter().find(|r| !monkeys.contains_key(**r)) {
+            return Err(ParseError::at(input, unknown, "a known monkey"));
+        }
+        match monkeys.get("root") {
+            Some(Monkey::Operation(..)) => Ok(monkeys),
+            _ => Err(ParseError::after(
+                input,
+                input.trim_end(),
+                "an operation for root",
+            )),
         }
     }
 
-    let task1 = get(&monkeys, "root");
-    println!("[day 21] task 1: {}", task1.unwrap());
+    fn part1(monkeys: &Self::Input) -> i64 {
+        get(monkeys, "root").unwrap()
+    }
 
-    monkeys.insert(String::from("humn"), Monkey::Human);
-    let (left, right) = if let Monkey::Operation(left, op, right) = monkeys.get("root").unwrap() {
-        (left, right)
-    } else {
-        panic!();
-    };
-    let left_value = get(&monkeys, left);
-    let right_value = get(&monkeys, right);
-    let human = if left_value.is_some() {
-        get_human_number(&monkeys, right, left_value.unwrap())
-    } else {
-        get_human_number(&monkeys, left, right_value.unwrap())
-    };
-    println!("[day 21] task 2: {}", human);
+    #[allow(clippy::unnecessary_unwrap)]
+    fn part2(monkeys: &Self::Input) -> i64 {
+        let (left, right) = match monkeys.get("root").unwrap() {
+            Monkey::Operation(left, _, right) => (left, right),
+            _ => panic!("nay"),
+        };
+
+        let mut monkeys = monkeys.clone();
+        monkeys.insert(String::from("humn"), Monkey::Human);
+        let left_value = get(&monkeys, left);
+        let right_value = get(&monkeys, right);
+        if left_value.is_some() {
+            get_human_number(&monkeys, right, left_value.unwrap())
+        } else {
+            get_human_number(&monkeys, left, right_value.unwrap())
+        }
+    }
 }
@@ -1,16 +1,22 @@
+use std::io;
+
 use crate::{
     array2d::Array2D,
+    heading::Heading,
+    image::Frames,
+    parse::{self, ParseError},
+    solution::Solution,
     vec2::{vec2, Vec2i32},
 };
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
-enum Direction {
+pub enum Direction {
     Left,
     Right,
     Straight(i32),
 }
 
-fn parse_directions(s: &str) -> Vec<Direction> {
+fn parse_directions(input: &str, s: &str) -> Result<Vec<Direction>, ParseError> {
     let mut s = s.trim();
     let mut ret = Vec::new();
     while !s.is_empty() {
@@ -21,97 +27,120 @@ fn parse_directions(s: &str) -> Vec<Direction> {
                 let l = s.find('L').unwrap_or(s.len());
                 let r = s.find('R').unwrap_or(s.len());
                 let count = l.min(r);
-                let steps = s[0..count].parse().unwrap();
+                let steps = parse::number(input, &s[0..count])?;
                 (Direction::Straight(steps), &s[count..])
             }
         };
         ret.push(dir);
         s = new_s;
     }
-    ret
+    Ok(ret)
 }
 
 #[derive(Clone, Copy, Default, PartialEq, Eq)]
-enum Tile {
+pub enum Tile {
     #[default]
     Void,
     Empty,
     Wall,
 }
 
-const RIGHT: i32 = 0;
-const DOWN: i32 = 1;
-const LEFT: i32 = 2;
-const UP: i32 = 3;
+/// Draws the walk over the map, with one frame per instruction.
+struct Walk {
+    frames: Frames,
+    image: Array2D<[u8; 3]>,
+}
+
+impl Walk {
+    fn new(frames: Frames, map: &Array2D<Tile>) -> Self {
+        let image = map.map(|tile| match tile {
+            Tile::Void => [0, 0, 0],
+            Tile::Empty => [224, 224, 224],
+            Tile::Wall => [96, 96, 96],
+        });
+        Self { frames, image }
+    }
+
+    fn step(&mut self, pos: Vec2i32) {
+        self.image.set(pos, [220, 40, 40]);
+    }
+
+    fn write_frame(&mut self) {
+        self.frames.write_ppm(&self.image, |rgb| *rgb);
+    }
 
-fn look_diff(look: i32) -> Vec2i32 {
-    match look {
-        RIGHT => vec2(1, 0),
-        DOWN => vec2(0, 1),
-        LEFT => vec2(-1, 0),
-        UP => vec2(0, -1),
-        _ => panic!("nay"),
+    fn finish(self) -> io::Result<usize> {
+        self.frames.finish()
     }
 }
 
-fn parse_map(lines: &[&str]) -> Array2D<Tile> {
-    let width = lines.iter().map(|line| line.len()).max().unwrap();
-    let height = lines.len();
-    let mut ret = Array2D::new(width, height);
-    ret.fill(Tile::Void);
-    for y in 0..height {
-        for (x, ch) in lines[y].chars().enumerate() {
-            let tile = match ch {
-                '.' => Tile::Empty,
-                '#' => Tile::Wall,
-                _ => Tile::Void,
-            };
-            ret.set((x, y), tile);
-        }
+fn parse_map(input: &str, s: &str) -> Result<Array2D<Tile>, ParseError> {
+    let (ret, _) = Array2D::from_str_map(input, s, "", |ch| match ch {
+        '.' => Some(Tile::Empty),
+        '#' => Some(Tile::Wall),
+        ' ' => Some(Tile::Void),
+        _ => None,
+    })?;
+    if !ret.slice(0).contains(&Tile::Empty) {
+        return Err(ParseError::at(input, s, "an open tile in the first row"));
     }
-    ret
+    Ok(ret)
 }
 
-fn traverse1(map: &Array2D<Tile>, directions: &[Direction]) -> (Vec2i32, i32) {
-    let size = vec2(map.width() as i32, map.height() as i32);
+fn traverse1(
+    map: &Array2D<Tile>,
+    directions: &[Direction],
+    mut walk: Option<&mut Walk>,
+) -> (Vec2i32, Heading) {
     let start_x = map.slice(0).iter().position(|t| *t == Tile::Empty).unwrap();
     let mut pos = vec2(start_x as i32, 0);
-    let mut look = RIGHT;
+    let mut look = Heading::East;
 
     for dir in directions {
         match dir {
-            Direction::Left => look = (look + 3) % 4,
-            Direction::Right => look = (look + 1) % 4,
+            Direction::Left => look = look.turn_left(),
+            Direction::Right => look = look.turn_right(),
             Direction::Straight(steps) => {
-                let diff = look_diff(look);
+                let diff = look.to_vec2();
                 for _ in 0..*steps {
-                    let mut new_pos = (pos + diff + size) % size;
+                    let mut new_pos = map.wrap(pos + diff);
                     while *map.at(new_pos) == Tile::Void {
-                        new_pos = (new_pos + diff + size) % size;
+                        new_pos = map.wrap(new_pos + diff);
                     }
                     if *map.at(new_pos) == Tile::Wall {
                         break;
                     } else {
                         pos = new_pos;
                     }
+                    if let Some(walk) = walk.as_mut() {
+                        walk.step(pos);
+                    }
                 }
             }
         }
+        if let Some(walk) = walk.as_mut() {
+            walk.write_frame();
+        }
     }
 
     (pos, look)
 }
 
-#[derive(Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 struct Wrap {
     source_face: Vec2i32,
-    source_look: i32,
+    source_look: Heading,
     target_face: Vec2i32,
-    target_look: i32,
+    target_look: Heading,
 }
 
 impl Wrap {
-    fn new(source_face: Vec2i32, source_look: i32, target_face: Vec2i32, target_look: i32) -> Self {
+    fn new(
+        source_face: Vec2i32,
+        source_look: Heading,
+        target_face: Vec2i32,
+        target_look: Heading,
+    ) -> Self {
         Self {
             source_face,
             source_look,
@@ -124,37 +153,32 @@ impl Wrap {
 fn traverse2(
     map: &Array2D<Tile>,
     directions: &[Direction],
-    faces: &[Vec2i32],
     face_size: i32,
     wraps: &[Wrap],
-) -> (Vec2i32, i32) {
-    let size = vec2(map.width() as i32, map.height() as i32);
+    mut walk: Option<&mut Walk>,
+) -> (Vec2i32, Heading) {
     let start_x = map.slice(0).iter().position(|t| *t == Tile::Empty).unwrap();
     let mut pos = vec2(start_x as i32, 0);
-    let mut look = RIGHT;
+    let mut look = Heading::East;
 
     for dir in directions {
         match dir {
-            Direction::Left => look = (look + 3) % 4,
-            Direction::Right => look = (look + 1) % 4,
+            Direction::Left => look = look.turn_left(),
+            Direction::Right => look = look.turn_right(),
             Direction::Straight(steps) => {
                 for _ in 0..*steps {
-                    let mut new_pos = pos + look_diff(look);
+                    let mut new_pos = pos + look;
                     let mut new_look = look;
 
                     // wrap around?
-                    if new_pos.lt(vec2(0, 0)).any()
-                        || new_pos.ge(size).any()
-                        || *map.at(new_pos) == Tile::Void
-                    {
+                    if map.get(new_pos).is_none_or(|tile| *tile == Tile::Void) {
                         let face = pos / face_size;
                         let ofs = pos - face * face_size;
                         let edge = match new_look {
-                            RIGHT => ofs.y,
-                            DOWN => face_size - 1 - ofs.x,
-                            LEFT => face_size - 1 - ofs.y,
-                            UP => ofs.x,
-                            _ => panic!("nay"),
+                            Heading::East => ofs.y,
+                            Heading::South => face_size - 1 - ofs.x,
+                            Heading::West => face_size - 1 - ofs.y,
+                            Heading::North => ofs.x,
                         };
 
                         let wrap = wraps
@@ -162,11 +186,10 @@ fn traverse2(
                             .find(|wrap| wrap.source_face == face && wrap.source_look == new_look)
                             .unwrap();
                         let new_ofs = match wrap.target_look {
-                            RIGHT => vec2(0, edge),
-                            DOWN => vec2(face_size - 1 - edge, 0),
-                            LEFT => vec2(face_size - 1, face_size - 1 - edge),
-                            UP => vec2(edge, face_size - 1),
-                            _ => panic!("nay"),
+                            Heading::East => vec2(0, edge),
+                            Heading::South => vec2(face_size - 1 - edge, 0),
+                            Heading::West => vec2(face_size - 1, face_size - 1 - edge),
+                            Heading::North => vec2(edge, face_size - 1),
                         };
                         new_pos = wrap.target_face * face_size + new_ofs;
                         new_look = wrap.target_look;
@@ -178,67 +201,155 @@ fn traverse2(
                         pos = new_pos;
                         look = new_look;
                     }
+                    if let Some(walk) = walk.as_mut() {
+                        walk.step(pos);
+                    }
                 }
             }
         }
+        if let Some(walk) = walk.as_mut() {
+            walk.write_frame();
+        }
     }
 
     (pos, look)
 }
 
-pub fn solve() {
-    let input = include_str!("inputs/22.txt");
-    let lines: Vec<&str> = input.split("\n").filter(|l| !l.trim().is_empty()).collect();
-
-    let map = parse_map(&lines[0..lines.len() - 1]);
-    let directions = parse_directions(lines.last().unwrap());
-
-    let task1 = traverse1(&map, &directions);
-    let task1 = (task1.0.y + 1) * 1000 + (task1.0.x + 1) * 4 + task1.1;
-    println!("[day 22] task 1: {}", task1);
-
-    // let face_size = 4;
-    // let faces = [vec2(2, 0), vec2(0, 1), vec2(1, 1), vec2(2, 1), vec2(2, 2), vec2(3, 2)];
-    // let mut wraps = vec![
-    //     Wrap::new(faces[0], RIGHT, faces[5], LEFT),
-    //     Wrap::new(faces[0], UP, faces[1], DOWN),
-    //     Wrap::new(faces[0], LEFT, faces[2], DOWN),
-    //     Wrap::new(faces[1], LEFT, faces[5], UP),
-    //     Wrap::new(faces[1], DOWN, faces[4], UP),
-    //     Wrap::new(faces[2], DOWN, faces[4], RIGHT),
-    //     Wrap::new(faces[3], RIGHT, faces[5], DOWN),
-    // ];
-
-    let face_size = 50;
-    let faces = [
-        vec2(1, 0),
-        vec2(2, 0),
-        vec2(1, 1),
-        vec2(1, 2),
-        vec2(0, 2),
-        vec2(0, 3),
-    ];
-    let mut wraps = vec![
-        Wrap::new(faces[0], UP, faces[5], RIGHT),
-        Wrap::new(faces[0], LEFT, faces[4], RIGHT),
-        Wrap::new(faces[1], UP, faces[5], UP),
-        Wrap::new(faces[1], RIGHT, faces[3], LEFT),
-        Wrap::new(faces[1], DOWN, faces[2], LEFT),
-        Wrap::new(faces[2], LEFT, faces[4], DOWN),
-        Wrap::new(faces[3], DOWN, faces[5], LEFT),
-    ];
-
-    for i in 0..wraps.len() {
-        let wrap = wraps[i];
-        wraps.push(Wrap::new(
-            wrap.target_face.into(),
-            (wrap.target_look + 2) % 4,
-            wrap.source_face.into(),
-            (wrap.source_look + 2) % 4,
-        ));
-    }
-
-    let task2 = traverse2(&map, &directions, &faces, face_size, &wraps);
-    let task2 = (task2.0.y + 1) * 1000 + (task2.0.x + 1) * 4 + task2.1;
-    println!("[day 22] task 2: {}", task2);
+fn password((pos, look): (Vec2i32, Heading)) -> i32 {
+    let facing = match look {
+        Heading::East => 0,
+        Heading::South => 1,
+        Heading::West => 2,
+        Heading::North => 3,
+    };
+    (pos.y + 1) * 1000 + (pos.x + 1) * 4 + facing
+}
+
+/// How the faces of the map are folded into a cube.
+pub struct Cube {
+    face_size: i32,
+    wraps: Vec<Wrap>,
+}
+
+impl Cube {
+    fn new(face_size: i32, mut wraps: Vec<Wrap>) -> Self {
+        for i in 0..wraps.len() {
+            let wrap = wraps[i];
+            wraps.push(Wrap::new(
+                wrap.target_face,
+                wrap.target_look.turn_around(),
+                wrap.source_face,
+                wrap.source_look.turn_around(),
+            ));
+        }
+        Self { face_size, wraps }
+    }
+
+    /// The folding of the puzzle input.
+    fn input() -> Self {
+        let faces = [
+            vec2(1, 0),
+            vec2(2, 0),
+            vec2(1, 1),
+            vec2(1, 2),
+            vec2(0, 2),
+            vec2(0, 3),
+        ];
+        let wraps = vec![
+            Wrap::new(faces[0], Heading::North, faces[5], Heading::East),
+            Wrap::new(faces[0], Heading::West, faces[4], Heading::East),
+            Wrap::new(faces[1], Heading::North, faces[5], Heading::North),
+            Wrap::new(faces[1], Heading::East, faces[3], Heading::West),
+            Wrap::new(faces[1], Heading::South, faces[2], Heading::West),
+            Wrap::new(faces[2], Heading::West, faces[4], Heading::South),
+            Wrap::new(faces[3], Heading::South, faces[5], Heading::West),
+        ];
+        Self::new(50, wraps)
+    }
+
+    /// The folding of the example input.
+    #[cfg(test)]
+    pub fn example() -> Self {
+        let faces = [
+            vec2(2, 0),
+            vec2(0, 1),
+            vec2(1, 1),
+            vec2(2, 1),
+            vec2(2, 2),
+            vec2(3, 2),
+        ];
+        let wraps = vec![
+            Wrap::new(faces[0], Heading::East, faces[5], Heading::West),
+            Wrap::new(faces[0], Heading::North, faces[1], Heading::South),
+            Wrap::new(faces[0], Heading::West, faces[2], Heading::South),
+            Wrap::new(faces[1], Heading::West, faces[5], Heading::North),
+            Wrap::new(faces[1], Heading::South, faces[4], Heading::North),
+            Wrap::new(faces[2], Heading::South, faces[4], Heading::East),
+            Wrap::new(faces[3], Heading::East, faces[5], Heading::South),
+        ];
+        Self::new(4, wraps)
+    }
+}
+
+pub fn cube_password((map, directions): &(Array2D<Tile>, Vec<Direction>), cube: &Cube) -> i32 {
+    password(traverse2(
+        map,
+        directions,
+        cube.face_size,
+        &cube.wraps,
+        None,
+    ))
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u32 = 22;
+    const INPUT: &'static str = include_str!("inputs/22.txt");
+
+    type Input = (Array2D<Tile>, Vec<Direction>);
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut paragraphs = parse::paragraphs(input);
+        let (map, path) = match (paragraphs.next(), paragraphs.next()) {
+            (Some(map), Some(path)) => (map, path),
+            _ => {
+                let err = ParseError::after(input, input.trim_end(), "a map and a path");
+                return Err(err);
+            }
+        };
+
+        Ok((parse_map(input, map)?, parse_directions(input, path)?))
+    }
+
+    fn part1((map, directions): &Self::Input) -> i32 {
+        password(traverse1(map, directions, None))
+    }
+
+    fn part2(input: &Self::Input) -> i32 {
+        cube_password(input, &Cube::input())
+    }
+
+    fn export((map, directions): &Self::Input) -> io::Result<()> {
+        if let Some(frames) = Frames::from_env("day22-flat")? {
+            let mut walk = Walk::new(frames, map);
+            traverse1(map, directions, Some(&mut walk));
+            walk.finish()?;
+        }
+        if let Some(frames) = Frames::from_env("day22-cube")? {
+            let cube = Cube::input();
+            let mut walk = Walk::new(frames, map);
+            traverse2(
+                map,
+                directions,
+                cube.face_size,
+                &cube.wraps,
+                Some(&mut walk),
+            );
+            walk.finish()?;
+        }
+        Ok(())
+    }
 }
@@ -1,127 +1,150 @@
-use crate::vec2::{vec2, Vec2i32};
-use std::collections::{HashMap, HashSet};
+use crate::{
+    array2d::Array2D,
+    heading::{Compass, Heading},
+    image::Frames,
+    infinite_grid::InfiniteGrid,
+    parse::ParseError,
+    rect::Rect,
+    solution::Solution,
+    vec2::Vec2i32,
+};
+use std::{collections::HashMap, io};
 
-struct Field {
-    elves: HashSet<Vec2i32>,
+/// The directions elves consider moving in, starting from a different one each round.
+const PROPOSAL_ORDER: [Heading; 4] = [Heading::North, Heading::South, Heading::West, Heading::East];
+
+#[derive(Clone)]
+pub struct Field {
+    elves: InfiniteGrid<bool>,
 }
 
 impl Field {
-    fn parse(input: &str) -> Self {
-        let mut elves = HashSet::new();
-        for line in input.split("\n").enumerate() {
-            for c in line.1.trim().chars().enumerate() {
-                match c.1 {
-                    '#' => {
-                        elves.insert(vec2(c.0 as i32, line.0 as i32));
-                    }
-                    '.' => {}
-                    _ => panic!("nay"),
-                }
-            }
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let (_, markers) = Array2D::from_str_map(input, input, "#", |ch| match ch {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+        let mut elves = InfiniteGrid::new();
+        for elf in markers.all('#') {
+            elves.set(elf, true);
         }
-        Self { elves }
+        Ok(Self { elves })
     }
 
-    fn round(&mut self, num: i32) -> bool {
-        let N = vec2(0, -1);
-        let S = vec2(0, 1);
-        let W = vec2(-1, 0);
-        let E = vec2(1, 0);
-        let dirs_main = [N, S, W, E];
-        let dirs_secondary = [(W, E), (W, E), (N, S), (N, S)];
-        let dirs_all = [N, S, W, E, N + W, N + E, S + W, S + E];
+    fn elves(&self) -> impl Iterator<Item = Vec2i32> + '_ {
+        self.elves
+            .iter()
+            .filter(|(_, elf)| **elf)
+            .map(|(pos, _)| pos)
+    }
 
+    fn round(&mut self, num: i32) -> bool {
         let mut proposals = HashMap::new();
-        for elf in &self.elves {
-            proposals.insert(*elf, -1);
+        for elf in self.elves() {
+            proposals.insert(elf, -1);
 
-            let empty = dirs_all
+            let empty = Vec2i32::NEIGHBORS8
                 .iter()
-                .all(|dir| !self.elves.contains(&(*elf + *dir)));
+                .all(|ofs| !self.elves.at(elf + *ofs));
             if empty {
                 continue;
             }
 
             for check in 0..4 {
                 let dir = ((check + num) % 4) as usize;
+                let heading = Compass::from(PROPOSAL_ORDER[dir]);
                 let check = [
-                    *elf + dirs_main[dir],
-                    *elf + dirs_main[dir] + dirs_secondary[dir].0,
-                    *elf + dirs_main[dir] + dirs_secondary[dir].1,
+                    elf + heading,
+                    elf + heading.rotate(-1),
+                    elf + heading.rotate(1),
                 ];
-                if !self.elves.contains(&check[0])
-                    && !self.elves.contains(&check[1])
-                    && !self.elves.contains(&check[2])
-                {
+                if check.iter().all(|pos| !self.elves.at(*pos)) {
                     *proposals.entry(check[0]).or_insert(0xFF) += 1;
-                    assert!(proposals.contains_key(elf));
-                    proposals.insert(*elf, dir as i32);
+                    assert!(proposals.contains_key(&elf));
+                    proposals.insert(elf, dir as i32);
                     break;
                 }
             }
         }
 
         let mut moved = false;
-        let mut elves = HashSet::new();
-        for elf in &self.elves {
-            let proposal = *proposals.get(elf).unwrap();
-            let mut new_pos = *elf;
+        let mut elves = InfiniteGrid::new();
+        for elf in self.elves() {
+            let proposal = *proposals.get(&elf).unwrap();
+            let mut new_pos = elf;
             if proposal >= 0 {
-                let check_pos = *elf + dirs_main[proposal as usize];
+                let check_pos = elf + PROPOSAL_ORDER[proposal as usize];
                 if *proposals.get(&check_pos).unwrap() == 0x100 {
                     new_pos = check_pos;
                     moved = true;
                 }
             }
-            elves.insert(new_pos);
+            elves.set(new_pos, true);
         }
 
         self.elves = elves;
         moved
     }
 
-    fn print(&self, title: &str) {
-        println!("{}", title);
-        for y in 0..12 {
-            let mut s = String::new();
-            for x in 0..14 {
-                if self.elves.contains(&vec2(x, y)) {
-                    s.push('#');
-                } else {
-                    s.push('.');
-                }
+    /// Writes the area covered by elves as an image, elves in white.
+    fn write_frame(&self, frames: &mut Frames) {
+        frames.write_pgm(&self.elves.to_array2d(), |elf| if *elf { 255 } else { 0 });
+    }
+
+    /// Runs the first `rounds` rounds, drawing the field into `frames` before and after each.
+    fn spread(&mut self, rounds: i32, mut frames: Option<&mut Frames>) {
+        for round in 0..rounds {
+            if let Some(frames) = frames.as_mut() {
+                self.write_frame(frames);
             }
-            println!("{}", s);
+            self.round(round);
+        }
+        if let Some(frames) = frames.as_mut() {
+            self.write_frame(frames);
         }
-        println!("");
     }
 
     fn empty_ground_tiles(&self) -> i32 {
-        let mut min = vec2(i32::MAX, i32::MAX);
-        let mut max = vec2(i32::MIN, i32::MIN);
-        for elf in &self.elves {
-            min = min.min(*elf);
-            max = max.max(*elf);
-        }
-        let size = max - min + vec2(1, 1);
-        size.x * size.y - self.elves.len() as i32
+        let area = Rect::from_points(self.elves()).map_or(0, |rect| rect.area());
+        area - self.elves().count() as i32
     }
 }
 
-pub fn solve() {
-    let input = include_str!("inputs/23.txt");
+pub struct Day23;
 
-    let mut field = Field::parse(input);
-    for round in 0..10 {
-        field.round(round);
+impl Solution for Day23 {
+    const DAY: u32 = 23;
+    const INPUT: &'static str = include_str!("inputs/23.txt");
+
+    type Input = Field;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Field, ParseError> {
+        Field::parse(input)
+    }
+
+    fn part1(field: &Field) -> i32 {
+        let mut field = field.clone();
+        field.spread(10, None);
+        field.empty_ground_tiles()
+    }
+
+    fn part2(field: &Field) -> i32 {
+        let mut field = field.clone();
+        let mut round = 0;
+        while field.round(round) {
+            round += 1;
+        }
+        round + 1
+    }
+
+    fn export(field: &Field) -> io::Result<()> {
+        if let Some(mut frames) = Frames::from_env("day23")? {
+            field.clone().spread(10, Some(&mut frames));
+            frames.finish()?;
+        }
+        Ok(())
     }
-    let task1 = field.empty_ground_tiles();
-    println!("[day 23] task 1: {}", task1);
-
-    // let mut field = Field::parse(input);
-    // let mut round = 0;
-    // while field.round(round) {
-    //     round += 1;
-    // }
-    // println!("[day 23] task 2: {}", round + 1);
 }
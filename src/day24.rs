@@ -1,8 +1,12 @@
 use std::collections::HashSet;
 
-use crate::vec2::{vec2, Vec2i32};
+use crate::{
+    parse::{self, ParseError},
+    solution::Solution,
+    vec2::{vec2, Vec2i32},
+};
 
-struct Field {
+pub struct Field {
     start: Vec2i32,
     end: Vec2i32,
     width: i32,
@@ -30,9 +34,8 @@ impl Field {
         }
 
         // check that we didn't run into a blizzard
-        let check = |blizz, ofs| {
-            let blizz = blizz + ofs + vec2(self.width, self.height) * step;
-            let blizz = blizz % vec2(self.width, self.height);
+        let check = |blizz: Vec2i32, ofs: Vec2i32| {
+            let blizz = (blizz + ofs).rem_euclid(vec2(self.width, self.height));
             blizz == pos
         };
 
@@ -78,83 +81,105 @@ impl Field {
     }
 }
 
-pub fn solve() {
-    let input = include_str!("inputs/24.txt");
-    let lines: Vec<&str> = input.split("\n").filter(|l| !l.trim().is_empty()).collect();
-    let width = lines[0].trim().len() as i32 - 2;
-    let height = lines.len() as i32 - 2;
-    let start = vec2(lines[0].chars().position(|c| c == '.').unwrap() as i32, 0);
-    let end = vec2(
-        lines
-            .last()
-            .unwrap()
-            .chars()
-            .position(|c| c == '.')
-            .unwrap() as i32,
-        height + 1,
-    );
-
-    let mut up = Vec::new();
-    let mut down = Vec::new();
-    let mut left = Vec::new();
-    let mut right = Vec::new();
-    for _ in 0..width {
-        up.push(Vec::new());
-        down.push(Vec::new());
-    }
-    for _ in 0..height {
-        left.push(Vec::new());
-        right.push(Vec::new());
+impl Field {
+    /// Returns the number of steps until the given number of journeys between start and end are done.
+    fn fastest_journeys(&self, journeys: i32) -> i32 {
+        let mut paths = HashSet::new();
+        paths.insert(State {
+            pos: self.start,
+            journey: 0,
+        });
+        let mut step = 0;
+        while !paths.iter().any(|state| state.journey == journeys) {
+            let mut new_paths = HashSet::new();
+            for path in &paths {
+                self.find_possible_paths(*path, step, &mut new_paths);
+            }
+            paths = new_paths;
+            step += 1;
+        }
+        step
     }
-    for y in lines.iter().enumerate() {
-        for x in y.1.chars().enumerate() {
-            let xy = vec2(x.0 as i32 - 1, y.0 as i32 - 1);
-            match x.1 {
-                '<' => left[y.0 - 1].push(xy),
-                '>' => right[y.0 - 1].push(xy),
-                '^' => up[x.0 - 1].push(xy),
-                'v' => down[x.0 - 1].push(xy),
-                _ => {}
-            };
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u32 = 24;
+    const INPUT: &'static str = include_str!("inputs/24.txt");
+
+    type Input = Field;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Field, ParseError> {
+        let (grid, markers) = parse::grid(input, input, "a tile", "<>^v", |ch| {
+            "#.<>^v".contains(ch).then_some(ch)
+        })?;
+        let lines: Vec<&str> = input.lines().filter(|l| !l.trim().is_empty()).collect();
+        if lines.len() < 3 {
+            return Err(ParseError::after(input, input.trim_end(), "a valley"));
         }
+        let width = grid.width() as i32 - 2;
+        let height = grid.height() as i32 - 2;
+        let gap = |y: usize| {
+            let x = grid.slice(y).iter().position(|c| *c == '.');
+            x.ok_or_else(|| ParseError::at(input, lines[y], "a gap in the wall"))
+        };
+        let start = vec2(gap(0)? as i32, 0);
+        let end = vec2(gap(lines.len() - 1)? as i32, height + 1);
+
+        for pos in "<>^v".chars().flat_map(|ch| markers.all(ch)) {
+            if pos.x == 0 || pos.y == 0 || pos.x == width + 1 || pos.y == height + 1 {
+                let line = lines[pos.y as usize];
+                return Err(ParseError::at(input, &line[pos.x as usize..], "a wall"));
+            }
+        }
+
+        let mut up = Vec::new();
+        let mut down = Vec::new();
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+        for _ in 0..width {
+            up.push(Vec::new());
+            down.push(Vec::new());
+        }
+        for _ in 0..height {
+            left.push(Vec::new());
+            right.push(Vec::new());
+        }
+        let ofs = vec2(1, 1);
+        for pos in markers.all('<') {
+            left[pos.y as usize - 1].push(pos - ofs);
+        }
+        for pos in markers.all('>') {
+            right[pos.y as usize - 1].push(pos - ofs);
+        }
+        for pos in markers.all('^') {
+            up[pos.x as usize - 1].push(pos - ofs);
+        }
+        for pos in markers.all('v') {
+            down[pos.x as usize - 1].push(pos - ofs);
+        }
+        let start = start - vec2(1, 1);
+        let end = end - vec2(1, 1);
+        Ok(Field {
+            left,
+            right,
+            up,
+            down,
+            start,
+            end,
+            width,
+            height,
+        })
     }
-    let start = start - vec2(1, 1);
-    let end = end - vec2(1, 1);
-    let field = Field {
-        left,
-        right,
-        up,
-        down,
-        start,
-        end,
-        width,
-        height,
-    };
-    let mut task1 = None;
-    let mut task2 = None;
-
-    let mut paths = HashSet::new();
-    paths.insert(State {
-        pos: start,
-        journey: 0,
-    });
-    let mut step = 0;
-    while task1.is_none() || task2.is_none() {
-        if task1.is_none() && paths.iter().any(|state| state.journey == 1) {
-            task1 = Some(step);
-        }
-
-        if task2.is_none() && paths.iter().any(|state| state.journey == 3) {
-            task2 = Some(step);
-        }
-
-        let mut new_paths = HashSet::new();
-        for path in &paths {
-            field.find_possible_paths(*path, step, &mut new_paths);
-        }
-        paths = new_paths;
-        step += 1;
+
+    fn part1(field: &Field) -> i32 {
+        field.fastest_journeys(1)
+    }
+
+    fn part2(field: &Field) -> i32 {
+        field.fastest_journeys(3)
     }
-    println!("[day 24] task 1: {:?}", task1.unwrap());
-    println!("[day 24] task 2: {:?}", task2.unwrap());
 }
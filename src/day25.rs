@@ -1,69 +1,65 @@
-use core::panic;
+use crate::{
+    parse::ParseError,
+    solution::{Solution, Unsolved},
+};
 
-fn from_snafu(str: &str) -> i64 {
+fn from_snafu(input: &str, str: &str) -> Result<i64, ParseError> {
+    let str = str.trim();
     let mut sum = 0;
     let mut base = 1;
-    for c in str.trim().chars().rev() {
+    for (i, c) in str.char_indices().rev() {
         let digit = match c {
             '0' => 0,
             '1' => 1,
             '2' => 2,
             '-' => -1,
             '=' => -2,
-            _ => panic!("nay"),
+            _ => return Err(ParseError::at(input, &str[i..], "a SNAFU digit")),
         };
         sum += digit * base;
         base *= 5;
     }
-    sum
+    Ok(sum)
 }
 
 fn to_snafu(mut n: i64) -> String {
+    // Take balanced digits from -2 to 2 off the end until nothing is left, so that zero still
+    // gets its one digit.
     let mut digits = Vec::new();
-    while n > 0 {
-        digits.push(n % 5);
-        n /= 5;
-    }
-    digits.push(0);
-
-    for i in 0..digits.len() {
-        while digits[i] > 2 {
-            digits[i] -= 5;
-            digits[i + 1] += 1;
+    loop {
+        let digit = (n + 2).rem_euclid(5) - 2;
+        digits.push(b"=-012"[(digit + 2) as usize] as char);
+        n = (n - digit) / 5;
+        if n == 0 {
+            break;
         }
     }
+    digits.iter().rev().collect()
+}
 
-    while *digits.last().unwrap() == 0 {
-        digits.pop();
-    }
+pub struct Day25;
 
-    digits
-        .iter()
-        .rev()
-        .map(|n| match *n {
-            0 => '0',
-            1 => '1',
-            2 => '2',
-            -1 => '-',
-            -2 => '=',
-            _ => panic!("nay"),
-        })
-        .collect()
-}
+impl Solution for Day25 {
+    const DAY: u32 = 25;
+    const INPUT: &'static str = include_str!("inputs/25.txt");
 
-pub fn solve() {
-    let input = include_str!("inputs/25.txt");
-    let numbers: Vec<i64> = input
-        .split("\n")
-        .filter_map(|l| {
-            if l.trim().is_empty() {
-                None
-            } else {
-                Some(from_snafu(l))
-            }
-        })
-        .collect();
+    type Input = Vec<i64>;
+    type Answer1 = String;
+    type Answer2 = Unsolved;
 
-    let task1 = to_snafu(numbers.iter().sum());
-    println!("[day 25] task 1: {}", task1);
+    fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+        input
+            .split("\n")
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| from_snafu(input, l))
+            .collect()
+    }
+
+    fn part1(numbers: &Vec<i64>) -> String {
+        to_snafu(numbers.iter().sum())
+    }
+
+    fn part2(_numbers: &Vec<i64>) -> Unsolved {
+        Unsolved
+    }
 }
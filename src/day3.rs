@@ -1,3 +1,5 @@
+use crate::{parse::ParseError, solution::Solution};
+
 fn priority(c: char) -> i32 {
     let n = c as i32;
     if n >= 'a' as i32 && n <= 'z' as i32 {
@@ -9,40 +11,64 @@ fn priority(c: char) -> i32 {
     }
 }
 
-pub fn solve() {
-    let lines: Vec<&str> = include_str!("inputs/3.txt").split('\n').collect();
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u32 = 3;
+    const INPUT: &'static str = include_str!("inputs/3.txt");
 
-    let mut task1 = 0;
-    let mut task2 = 0;
+    type Input = Vec<String>;
+    type Answer1 = i32;
+    type Answer2 = i32;
 
-    for line in &lines {
-        let sz = line.len() / 2;
-        let comp1 = &line[0..sz];
-        let comp2 = &line[sz..2 * sz];
-        for c in comp1.chars() {
-            if comp2.contains(c) {
-                task1 += priority(c);
-                break;
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+        input
+            .split('\n')
+            .map(|line| {
+                match line
+                    .char_indices()
+                    .find(|(_, ch)| !ch.is_ascii_alphabetic())
+                {
+                    Some((i, _)) => Err(ParseError::at(input, &line[i..], "a letter")),
+                    None => Ok(String::from(line)),
+                }
+            })
+            .collect()
+    }
+
+    fn part1(lines: &Vec<String>) -> i32 {
+        let mut task1 = 0;
+        for line in lines {
+            let sz = line.len() / 2;
+            let comp1 = &line[0..sz];
+            let comp2 = &line[sz..2 * sz];
+            for c in comp1.chars() {
+                if comp2.contains(c) {
+                    task1 += priority(c);
+                    break;
+                }
             }
         }
+        task1
     }
 
-    for i in 0..lines.len() / 3 {
-        let a = lines[3 * i];
-        let b = lines[3 * i + 1];
-        let c = lines[3 * i + 2];
-        for i in 0..52 {
-            let ch = if i < 26 {
-                (i + 'a' as u8) as char
-            } else {
-                (i + 'A' as u8 - 26) as char
-            };
-            if a.contains(ch) && b.contains(ch) && c.contains(ch) {
-                task2 += (i + 1) as i32;
+    fn part2(lines: &Vec<String>) -> i32 {
+        let mut task2 = 0;
+        for i in 0..lines.len() / 3 {
+            let a = &lines[3 * i];
+            let b = &lines[3 * i + 1];
+            let c = &lines[3 * i + 2];
+            for i in 0..52 {
+                let ch = if i < 26 {
+                    (i + b'a') as char
+                } else {
+                    (i + b'A' - 26) as char
+                };
+                if a.contains(ch) && b.contains(ch) && c.contains(ch) {
+                    task2 += (i + 1) as i32;
+                }
             }
         }
+        task2
     }
-
-    println!("[day 03] task 1: {}", task1);
-    println!("[day 03] task 2: {}", task2);
 }
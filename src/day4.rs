@@ -1,45 +1,58 @@
 use std::ops::Range;
 
-fn contains_range(a: &Range<i32>, b: &Range<i32>) -> bool {
-    (a.start <= b.start) && (a.end >= b.end)
-}
+use crate::{
+    interval_set::IntervalSet,
+    parse::{self, ParseError},
+    solution::Solution,
+};
 
-fn overlaps(a: &Range<i32>, b: &Range<i32>) -> bool {
-    (a.end > b.start) && (b.end > a.start)
+fn parse_range(input: &str, range: &str) -> Result<Range<i32>, ParseError> {
+    let (a, b) = parse::split_once(input, range, "-")?;
+    let a: i32 = parse::number(input, a)?;
+    let b: i32 = parse::number(input, b)?;
+    Ok(a..(b + 1))
 }
 
-fn parse_range(range: &str) -> Range<i32> {
-    let mut parts = range.split("-");
-    let a = parts.next().unwrap().parse::<i32>().unwrap();
-    let b = parts.next().unwrap().parse::<i32>().unwrap();
-    a..(b + 1)
+fn parse_range_pair(input: &str, line: &str) -> Result<(Range<i32>, Range<i32>), ParseError> {
+    let (a, b) = parse::split_once(input, line, ",")?;
+    Ok((parse_range(input, a)?, parse_range(input, b)?))
 }
 
-fn parse_range_pair(line: &str) -> (Range<i32>, Range<i32>) {
-    let mut parts = line.split(",");
-    let a = parse_range(parts.next().unwrap());
-    let b = parse_range(parts.next().unwrap());
-    (a, b)
-}
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u32 = 4;
+    const INPUT: &'static str = include_str!("inputs/4.txt");
+
+    type Input = Vec<(Range<i32>, Range<i32>)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .split('\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| parse_range_pair(input, line))
+            .collect()
+    }
+
+    fn part1(range_pairs: &Self::Input) -> usize {
+        range_pairs
+            .iter()
+            .filter(|(a, b)| {
+                IntervalSet::from(a.clone()).contains_range(b)
+                    || IntervalSet::from(b.clone()).contains_range(a)
+            })
+            .count()
+    }
 
-pub fn solve() {
-    let lines = include_str!("inputs/4.txt").split('\n');
-    let range_pairs: Vec<(Range<i32>, Range<i32>)> = lines
-        .filter_map(|line| {
-            if line.is_empty() {
-                None
-            } else {
-                Some(parse_range_pair(line))
-            }
-        })
-        .collect();
-
-    let task1 = range_pairs
-        .iter()
-        .filter(|(a, b)| contains_range(a, b) || contains_range(b, a))
-        .count();
-    let task2 = range_pairs.iter().filter(|(a, b)| overlaps(a, b)).count();
-
-    println!("[day 04] task 1: {}", task1);
-    println!("[day 04] task 2: {}", task2);
+    fn part2(range_pairs: &Self::Input) -> usize {
+        range_pairs
+            .iter()
+            .filter(|(a, b)| {
+                let a = IntervalSet::from(a.clone());
+                !a.intersection(&IntervalSet::from(b.clone())).is_empty()
+            })
+            .count()
+    }
 }
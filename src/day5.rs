@@ -1,52 +1,111 @@
-use text_io::scan;
-
-const STACKS: usize = 9;
-
-pub fn solve() {
-    let lines: Vec<&str> = include_str!("inputs/5.txt").split('\n').collect();
-
-    let mut stacks1: [Vec<char>; STACKS] = Default::default();
-    let mut stacks2: [Vec<char>; STACKS] = Default::default();
-    let mut init_done = false;
-
-    for line in lines {
-        if line.is_empty() {
-            init_done = true;
-        } else {
-            if !init_done {
-                for x in 0..STACKS {
-                    let ch = line.chars().nth(1 + 4 * x).unwrap();
-                    if ch.is_uppercase() {
-                        stacks1[x].insert(0, ch);
-                        stacks2[x].insert(0, ch);
-                    }
+use crate::{
+    parse::{self, ParseError},
+    solution::Solution,
+};
+
+type Stacks = Vec<Vec<char>>;
+
+pub struct Move {
+    num: usize,
+    from: usize,
+    to: usize,
+}
+
+fn parse_stack(input: &str, token: &str, stacks: usize) -> Result<usize, ParseError> {
+    match token.parse::<usize>() {
+        Ok(stack) if (1..=stacks).contains(&stack) => Ok(stack - 1),
+        _ => Err(ParseError::at(
+            input,
+            token,
+            format!("a stack from 1 to {}", stacks),
+        )),
+    }
+}
+
+/// The crates on top of the stacks, skipping empty ones.
+fn top_crates(stacks: &Stacks) -> String {
+    stacks.iter().filter_map(|stack| stack.last()).collect()
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u32 = 5;
+    const INPUT: &'static str = include_str!("inputs/5.txt");
+
+    type Input = (Stacks, Vec<Move>);
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut paragraphs = parse::paragraphs(input);
+        let drawing = paragraphs
+            .next()
+            .ok_or_else(|| ParseError::at(input, input, "a drawing of the stacks"))?;
+        let procedure = paragraphs.next().unwrap_or("");
+
+        // The last line of the drawing numbers the stacks.
+        let numbers = drawing.lines().last().unwrap_or("");
+        let count = numbers.split_whitespace().count();
+        if count == 0 {
+            return Err(ParseError::at(input, numbers, "the numbers of the stacks"));
+        }
+
+        let mut stacks: Stacks = vec![Vec::new(); count];
+        for line in drawing.lines() {
+            for (x, stack) in stacks.iter_mut().enumerate() {
+                let ch = line.chars().nth(1 + 4 * x).unwrap_or(' ');
+                if ch.is_uppercase() {
+                    stack.insert(0, ch);
                 }
-            } else {
-                let num: usize;
-                let from: usize;
-                let to: usize;
-                scan!(line.bytes() => "move {} from {} to {}", num, from, to);
-                let from = from - 1;
-                let to = to - 1;
-
-                for _ in 0..num {
-                    let v = stacks1[from].pop().unwrap();
-                    stacks1[to].push(v);
+            }
+        }
+
+        // Both parts move the same number of crates between the same stacks, so tracking the
+        // heights here guarantees that no move takes more crates than its stack holds.
+        let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+        let moves = parse::lines(procedure)
+            .map(|line| {
+                let [num, from, to] = parse::pattern(input, line, "move {} from {} to {}")?;
+                let m = Move {
+                    num: parse::number(input, num)?,
+                    from: parse_stack(input, from, count)?,
+                    to: parse_stack(input, to, count)?,
+                };
+                if heights[m.from] < m.num {
+                    return Err(ParseError::at(
+                        input,
+                        num,
+                        format!("a move of at most {} crates", heights[m.from]),
+                    ));
                 }
+                heights[m.from] -= m.num;
+                heights[m.to] += m.num;
+                Ok(m)
+            })
+            .collect::<Result<_, _>>()?;
 
-                let start = stacks2[from].len() - num;
-                let items: Vec<char> = stacks2[from].drain(start..).collect();
-                stacks2[to].extend(&items);
+        Ok((stacks, moves))
+    }
+
+    fn part1((stacks, moves): &Self::Input) -> String {
+        let mut stacks = stacks.clone();
+        for m in moves {
+            for _ in 0..m.num {
+                let v = stacks[m.from].pop().unwrap();
+                stacks[m.to].push(v);
             }
         }
+        top_crates(&stacks)
     }
 
-    let mut task1 = String::new();
-    let mut task2 = String::new();
-    for i in 0..STACKS {
-        task1.push(*stacks1[i].last().unwrap());
-        task2.push(*stacks2[i].last().unwrap());
+    fn part2((stacks, moves): &Self::Input) -> String {
+        let mut stacks = stacks.clone();
+        for m in moves {
+            let start = stacks[m.from].len() - m.num;
+            let items: Vec<char> = stacks[m.from].drain(start..).collect();
+            stacks[m.to].extend(&items);
+        }
+        top_crates(&stacks)
     }
-    println!("[day 05] task 1: {}", task1);
-    println!("[day 05] task 2: {}", task2);
 }
@@ -1,6 +1,6 @@
-use crate::timer::Timer;
+use crate::{parse::ParseError, solution::Solution};
 
-fn adjust(counts: &mut [i32; 26], unique: &mut i32, index: usize, add: bool) {
+fn adjust(counts: &mut [i32; 26], unique: &mut usize, index: usize, add: bool) {
     counts[index] += if add { 1 } else { -1 };
     if add && counts[index] == 1 {
         *unique += 1;
@@ -10,35 +10,51 @@ fn adjust(counts: &mut [i32; 26], unique: &mut i32, index: usize, add: bool) {
     }
 }
 
-pub fn solve() {
-    let data = include_str!("inputs/6.txt").trim();
-
-    let char_index: Vec<usize> = data.chars().map(|c| c as usize - 'a' as usize).collect();
-
-    let mut counts1 = [0; 26];
-    let mut counts2 = [0; 26];
-    let mut unique1 = 0;
-    let mut unique2 = 0;
-    let mut task1 = None;
-    let mut task2 = None;
-    for i in 0..data.len() {
-        if i >= 4 {
-            adjust(&mut counts1, &mut unique1, char_index[i - 4], false);
+/// The number of characters up to the end of the first `len` distinct ones, or `None` if there
+/// are no such characters.
+fn find_marker(char_index: &[usize], len: usize) -> Option<usize> {
+    let mut counts = [0; 26];
+    let mut unique = 0;
+    for i in 0..char_index.len() {
+        if i >= len {
+            adjust(&mut counts, &mut unique, char_index[i - len], false);
         }
-        if i >= 14 {
-            adjust(&mut counts2, &mut unique2, char_index[i - 14], false);
+        adjust(&mut counts, &mut unique, char_index[i], true);
+        if unique == len {
+            return Some(i + 1);
         }
+    }
+    None
+}
 
-        adjust(&mut counts1, &mut unique1, char_index[i], true);
-        adjust(&mut counts2, &mut unique2, char_index[i], true);
+pub struct Day6;
 
-        if unique1 == 4 && task1.is_none() {
-            task1 = Some(i + 1);
-        }
-        if unique2 == 14 && task2.is_none() {
-            task2 = Some(i + 1);
-        }
+impl Solution for Day6 {
+    const DAY: u32 = 6;
+    const INPUT: &'static str = include_str!("inputs/6.txt");
+
+    type Input = Vec<usize>;
+    type Answer1 = Option<usize>;
+    type Answer2 = Option<usize>;
+
+    fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+        let line = input.trim();
+        line.char_indices()
+            .map(|(i, c)| {
+                if c.is_ascii_lowercase() {
+                    Ok(c as usize - 'a' as usize)
+                } else {
+                    Err(ParseError::at(input, &line[i..], "a lowercase letter"))
+                }
+            })
+            .collect()
+    }
+
+    fn part1(char_index: &Vec<usize>) -> Option<usize> {
+        find_marker(char_index, 4)
+    }
+
+    fn part2(char_index: &Vec<usize>) -> Option<usize> {
+        find_marker(char_index, 14)
     }
-    println!("[day 06] task 1: {}", task1.unwrap());
-    println!("[day 06] task 2: {}", task2.unwrap());
 }
@@ -1,14 +1,15 @@
-struct Directory {
-    name: String,
-    files: Vec<(String, usize)>,
+use crate::{
+    parse::{self, ParseError},
+    solution::Solution,
+};
+
+pub struct Directory {
     dirs: Vec<Directory>,
     size: usize,
 }
 
 impl Directory {
-    fn create(name: &str, input: &[&str], mut index: usize) -> (Self, usize) {
-        let name = name.to_string();
-        let mut files = Vec::new();
+    fn create(source: &str, input: &[&str], mut index: usize) -> Result<(Self, usize), ParseError> {
         let mut dirs = Vec::new();
         let mut size = 0;
 
@@ -21,8 +22,7 @@ impl Directory {
             }
 
             if line.starts_with("$ cd ") {
-                let name = &line[5..];
-                let dir = Self::create(name, input, index + 1);
+                let dir = Self::create(source, input, index + 1)?;
                 size += dir.0.size;
                 dirs.push(dir.0);
                 index = dir.1;
@@ -33,26 +33,19 @@ impl Directory {
                 index += 1;
                 while index < input.len() && !input[index].starts_with("$ ") {
                     if !input[index].starts_with("dir") {
-                        let mut parts = input[index].split(" ");
-                        let file_size = parts.next().unwrap().parse().unwrap();
-                        let name = parts.next().unwrap().to_string();
-                        files.push((name, file_size));
+                        let (file_size, _) = parse::split_once(source, input[index], " ")?;
+                        let file_size: usize = parse::number(source, file_size)?;
                         size += file_size;
                     }
                     index += 1;
                 }
+                continue;
             }
+
+            return Err(ParseError::at(source, line, "'$ cd' or '$ ls'"));
         }
 
-        (
-            Self {
-                name,
-                files,
-                dirs,
-                size,
-            },
-            index,
-        )
+        Ok((Self { dirs, size }, index))
     }
 
     fn task1(&self) -> usize {
@@ -78,17 +71,32 @@ impl Directory {
     }
 }
 
-pub fn solve() {
-    let mut lines: Vec<&str> = include_str!("inputs/7.txt").split('\n').collect();
-    lines.retain(|line| !line.trim().is_empty());
-    let root = Directory::create("/", &lines, 1).0;
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u32 = 7;
+    const INPUT: &'static str = include_str!("inputs/7.txt");
+
+    type Input = Directory;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    let used_space = 70000000 - root.size;
-    let required_space = 30000000 - used_space;
+    fn parse(input: &str) -> Result<Directory, ParseError> {
+        let mut lines: Vec<&str> = input.split('\n').collect();
+        lines.retain(|line| !line.trim().is_empty());
+        match lines.first() {
+            Some(&"$ cd /") => Ok(Directory::create(input, &lines, 1)?.0),
+            _ => Err(ParseError::at(input, input, "'$ cd /'")),
+        }
+    }
 
-    println!("[day 07] task 1: {}", root.task1());
-    println!(
-        "[day 07] task 2: {}",
+    fn part1(root: &Directory) -> usize {
+        root.task1()
+    }
+
+    fn part2(root: &Directory) -> usize {
+        let used_space = 70000000 - root.size;
+        let required_space = 30000000 - used_space;
         root.find_smallest_over(required_space)
-    );
+    }
 }
@@ -1,81 +1,83 @@
-use crate::array2d::Array2D;
+use crate::{
+    array2d::Array2D,
+    parse::{self, ParseError},
+    solution::Solution,
+};
 
-pub fn solve() {
-    let data = include_str!("inputs/8.txt");
-    let lines: Vec<&str> = data.split('\n').filter(|s| !s.trim().is_empty()).collect();
-
-    let width = lines[0].len();
-    let height = lines.len();
-
-    let mut heights = Array2D::new(width, height);
-    let mut visible = Array2D::new(width, height);
-    for j in 0..height {
-        for i in 0..width {
-            heights.set((i, j), lines[j][i..i + 1].parse::<i32>().unwrap());
-            visible.set((i, j), false);
+/// Marks the trees that are visible when looking at each row from the left.
+fn visible_from_left(heights: &Array2D<i32>) -> Array2D<bool> {
+    let mut visible = Array2D::new(heights.width(), heights.height());
+    for (row, visible) in heights.rows().zip(visible.rows_mut()) {
+        let mut min_height = -1;
+        for (h, visible) in row.iter().zip(visible) {
+            if *h > min_height {
+                *visible = true;
+                min_height = *h;
+            }
         }
     }
+    visible
+}
 
-    let mut check = |x, y, min_height: &mut i32| {
-        let h = *heights.at((x, y));
-        if h > *min_height {
-            visible.set((x, y), true);
-            *min_height = h;
+/// Counts the trees that can be seen from a tree of height `h`, up to the first one that is at
+/// least as tall.
+fn viewing_distance<'a>(h: i32, trees: impl Iterator<Item = &'a i32>) -> usize {
+    let mut count = 0;
+    for tree in trees {
+        count += 1;
+        if *tree >= h {
+            break;
         }
-    };
+    }
+    count
+}
 
-    for x in 0..width {
-        let mut h = -1;
-        for y in 0..height {
-            check(x, y, &mut h)
-        }
-        let mut h = -1;
-        for y in (0..height).rev() {
-            check(x, y, &mut h)
-        }
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u32 = 8;
+    const INPUT: &'static str = include_str!("inputs/8.txt");
+
+    type Input = Array2D<i32>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Array2D<i32>, ParseError> {
+        let (heights, _) = parse::grid(input, input, "a digit", "", |ch| {
+            ch.to_digit(10).map(|height| height as i32)
+        })?;
+        Ok(heights)
     }
 
-    for y in 0..height {
-        let mut h = -1;
-        for x in 0..width {
-            check(x, y, &mut h)
-        }
-        let mut h = -1;
-        for x in (0..width).rev() {
-            check(x, y, &mut h)
-        }
+    fn part1(heights: &Array2D<i32>) -> usize {
+        let views = [
+            visible_from_left(heights),
+            visible_from_left(&heights.flip_x()).flip_x(),
+            visible_from_left(&heights.transpose()).transpose(),
+            visible_from_left(&heights.transpose().flip_x())
+                .flip_x()
+                .transpose(),
+        ];
+
+        (0..heights.height())
+            .flat_map(|y| (0..heights.width()).map(move |x| (x, y)))
+            .filter(|pos| views.iter().any(|view| *view.at(pos)))
+            .count()
     }
 
-    let task1 = visible.iter().filter(|v| **v).count();
-    let mut task2 = 0;
+    fn part2(heights: &Array2D<i32>) -> usize {
+        let mut task2 = 0;
 
-    for y0 in 0..height {
-        for x0 in 0..width {
-            let h = heights.at((x0, y0));
-            let mut l = x0 - 1.min(x0);
-            let mut r = (x0 + 1).min(width - 1);
-            let mut u = y0 - 1.min(y0);
-            let mut d = (y0 + 1).min(height - 1);
-            while l > 0 && heights.at((l, y0)) < h {
-                l -= 1;
-            }
-            while r < width - 1 && heights.at((r, y0)) < h {
-                r += 1;
+        for (y0, row) in heights.rows().enumerate() {
+            for (x0, h) in row.iter().enumerate() {
+                let l = viewing_distance(*h, row[..x0].iter().rev());
+                let r = viewing_distance(*h, row[x0 + 1..].iter());
+                let u = viewing_distance(*h, heights.column(x0).take(y0).rev());
+                let d = viewing_distance(*h, heights.column(x0).skip(y0 + 1));
+                task2 = task2.max(l * r * u * d);
             }
-            while u > 0 && heights.at((x0, u)) < h {
-                u -= 1;
-            }
-            while d < height - 1 && heights.at((x0, d)) < h {
-                d += 1;
-            }
-            let l = x0 - l;
-            let r = r - x0;
-            let u = y0 - u;
-            let d = d - y0;
-            task2 = task2.max(l * r * u * d);
         }
-    }
 
-    println!("[day 08] task 1: {}", task1);
-    println!("[day 08] task 1: {}", task2);
+        task2
+    }
 }
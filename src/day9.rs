@@ -1,77 +1,63 @@
 use std::collections::HashMap;
 
-enum Direction {
-    Left,
-    Right,
-    Up,
-    Down,
-}
-
-impl Direction {
-    fn from(s: &str) -> Self {
-        match s {
-            "L" => Direction::Left,
-            "R" => Direction::Right,
-            "U" => Direction::Up,
-            "D" => Direction::Down,
-            _ => panic!("nay"),
-        }
-    }
-}
-
-pub fn solve() {
-    let data = include_str!("inputs/9.txt");
-    let commands: Vec<(Direction, i32)> = data
-        .split('\n')
-        .filter(|s| !s.trim().is_empty())
-        .map(|line| {
-            let mut parts = line.split(' ');
-            let dir = Direction::from(parts.next().unwrap());
-            let count = parts.next().unwrap().parse::<i32>().unwrap();
-            (dir, count)
-        })
-        .collect();
+use crate::{
+    heading::Heading,
+    parse::{self, ParseError},
+    solution::Solution,
+    vec2::Vec2i32,
+};
 
-    let mut knot = [(0i32, 0i32); 10];
-
-    let mut visited1 = HashMap::from([((0, 0), true)]);
-    let mut visited9 = HashMap::from([((0, 0), true)]);
+fn simulate(commands: &[(Heading, i32)], knots: usize) -> usize {
+    let mut knot = vec![Vec2i32::default(); knots];
+    let mut visited = HashMap::from([(Vec2i32::default(), true)]);
 
     for command in commands {
         for _ in 0..command.1 {
-            let diff = match command.0 {
-                Direction::Left => (-1, 0),
-                Direction::Right => (1, 0),
-                Direction::Up => (0, 1),
-                Direction::Down => (0, -1),
-            };
-
-            knot[0].0 += diff.0;
-            knot[0].1 += diff.1;
+            knot[0] += command.0;
 
-            for i in 0..9 {
+            for i in 0..knots - 1 {
                 let h = knot[i];
                 let t = &mut knot[i + 1];
-                let diff = (h.0 - t.0).abs().max((h.1 - t.1).abs());
-                if diff > 1 {
-                    if h.1 == t.1 {
-                        t.0 += (h.0 - t.0) / 2;
-                    } else if h.0 == t.0 {
-                        t.1 += (h.1 - t.1) / 2;
-                    } else {
-                        assert!((h.0 - t.0) != 0);
-                        assert!((h.1 - t.1) != 0);
-                        t.0 += (h.0 - t.0).signum();
-                        t.1 += (h.1 - t.1).signum();
-                    }
+                // a knot two steps away always moves one step towards the head, diagonally
+                // unless they are in line
+                if h.chebyshev(*t) > 1 {
+                    *t += (h - *t).signum();
                 }
             }
 
-            visited1.insert(knot[1], true);
-            visited9.insert(knot[9], true);
+            visited.insert(knot[knots - 1], true);
         }
     }
 
-    println!("[day 09] task 1: {}", visited1.len());
-    println!("[day 09] task 2: {}", visited9.len());
+    visited.len()
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u32 = 9;
+    const INPUT: &'static str = include_str!("inputs/9.txt");
+
+    type Input = Vec<(Heading, i32)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .split('\n')
+            .filter(|s| !s.trim().is_empty())
+            .map(|line| {
+                let (dir, count) = parse::split_once(input, line, " ")?;
+                Ok((Heading::parse(input, dir)?, parse::number(input, count)?))
+            })
+            .collect()
+    }
+
+    fn part1(commands: &Self::Input) -> usize {
+        simulate(commands, 2)
+    }
+
+    fn part2(commands: &Self::Input) -> usize {
+        simulate(commands, 10)
+    }
 }
@@ -1,3 +1,5 @@
+#![cfg_attr(not(test), allow(dead_code))]
+
 use std::fmt::Debug;
 use std::slice::Iter;
 use std::slice::IterMut;
@@ -0,0 +1,203 @@
+use crate::{
+    parse::ParseError,
+    vec2::{vec2, Vec2i32},
+};
+
+/// A cardinal direction on a grid with y pointing down, so north is `(0, -1)`. The variants are
+/// in clockwise order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Heading {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Heading {
+    /// The four directions, clockwise from north.
+    pub const ALL: [Self; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    /// Reads a direction from a compass letter (`NESW`), an arrow (`^>v<`) or a relative letter
+    /// (`URDL`).
+    pub fn from_char(ch: char) -> Option<Self> {
+        match ch {
+            'N' | '^' | 'U' => Some(Self::North),
+            'E' | '>' | 'R' => Some(Self::East),
+            'S' | 'v' | 'D' => Some(Self::South),
+            'W' | '<' | 'L' => Some(Self::West),
+            _ => None,
+        }
+    }
+
+    /// Parses `s`, which has to be a single character accepted by `from_char`.
+    pub fn parse(input: &str, s: &str) -> Result<Self, ParseError> {
+        let mut chars = s.chars();
+        match (chars.next().and_then(Self::from_char), chars.next()) {
+            (Some(heading), None) => Ok(heading),
+            _ => Err(ParseError::at(input, s, "a direction")),
+        }
+    }
+
+    /// Turns clockwise by `quarters` steps of 90°, or counter-clockwise if negative.
+    pub fn rotate(self, quarters: i32) -> Self {
+        Self::ALL[(self as i32 + quarters).rem_euclid(4) as usize]
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.rotate(-1)
+    }
+
+    pub fn turn_right(self) -> Self {
+        self.rotate(1)
+    }
+
+    pub fn turn_around(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// The offset of one step in this direction.
+    pub fn to_vec2(self) -> Vec2i32 {
+        match self {
+            Self::North => vec2(0, -1),
+            Self::East => vec2(1, 0),
+            Self::South => vec2(0, 1),
+            Self::West => vec2(-1, 0),
+        }
+    }
+}
+
+/// One of the eight compass directions: the cardinal ones and the diagonals between them, in
+/// clockwise order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Compass {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Compass {
+    /// All eight directions, clockwise from north.
+    pub const ALL: [Self; 8] = [
+        Self::North,
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::South,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
+    /// Reads a cardinal direction from any character accepted by `Heading::from_char`.
+    pub fn from_char(ch: char) -> Option<Self> {
+        Heading::from_char(ch).map(Self::from)
+    }
+
+    /// Parses `s`, which is either a diagonal (`NE`, `SE`, `SW`, `NW`) or a single character
+    /// accepted by `from_char`.
+    pub fn parse(input: &str, s: &str) -> Result<Self, ParseError> {
+        let compass = match s {
+            "NE" => Some(Self::NorthEast),
+            "SE" => Some(Self::SouthEast),
+            "SW" => Some(Self::SouthWest),
+            "NW" => Some(Self::NorthWest),
+            _ => Heading::parse(input, s).ok().map(Self::from),
+        };
+        compass.ok_or_else(|| ParseError::at(input, s, "a compass direction"))
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// Turns clockwise by `eighths` steps of 45°, or counter-clockwise if negative.
+    pub fn rotate(self, eighths: i32) -> Self {
+        Self::ALL[(self as i32 + eighths).rem_euclid(8) as usize]
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.rotate(-2)
+    }
+
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    pub fn turn_around(self) -> Self {
+        self.rotate(4)
+    }
+
+    /// The offset of one step in this direction.
+    pub fn to_vec2(self) -> Vec2i32 {
+        match self {
+            Self::North => vec2(0, -1),
+            Self::NorthEast => vec2(1, -1),
+            Self::East => vec2(1, 0),
+            Self::SouthEast => vec2(1, 1),
+            Self::South => vec2(0, 1),
+            Self::SouthWest => vec2(-1, 1),
+            Self::West => vec2(-1, 0),
+            Self::NorthWest => vec2(-1, -1),
+        }
+    }
+}
+
+impl From<Heading> for Compass {
+    fn from(heading: Heading) -> Self {
+        match heading {
+            Heading::North => Self::North,
+            Heading::East => Self::East,
+            Heading::South => Self::South,
+            Heading::West => Self::West,
+        }
+    }
+}
+
+/// Fails with the direction itself if it is diagonal.
+impl TryFrom<Compass> for Heading {
+    type Error = Compass;
+
+    fn try_from(compass: Compass) -> Result<Self, Compass> {
+        match compass {
+            Compass::North => Ok(Self::North),
+            Compass::East => Ok(Self::East),
+            Compass::South => Ok(Self::South),
+            Compass::West => Ok(Self::West),
+            Compass::NorthEast | Compass::SouthEast | Compass::SouthWest | Compass::NorthWest => {
+                Err(compass)
+            }
+        }
+    }
+}
+
+/// Lets a direction be used as the offset of one step.
+macro_rules! step_ops {
+    ($dir:ident) => {
+        impl From<$dir> for Vec2i32 {
+            fn from(dir: $dir) -> Self {
+                dir.to_vec2()
+            }
+        }
+
+        impl std::ops::Add<$dir> for Vec2i32 {
+            type Output = Self;
+            fn add(self, rhs: $dir) -> Self {
+                self + rhs.to_vec2()
+            }
+        }
+
+        impl std::ops::AddAssign<$dir> for Vec2i32 {
+            fn add_assign(&mut self, rhs: $dir) {
+                *self += rhs.to_vec2();
+            }
+        }
+    };
+}
+
+step_ops!(Heading);
+step_ops!(Compass);
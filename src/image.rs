@@ -0,0 +1,96 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::array2d::Array2D;
+
+/// The environment variable naming the directory that simulations write their frames to.
+pub const FRAMES_VAR: &str = "AOC_FRAMES";
+
+impl<T: Default + Clone> Array2D<T> {
+    /// Encodes the array as a binary PPM image, with one pixel per cell coloured by `f`.
+    pub fn to_ppm(&self, f: impl Fn(&T) -> [u8; 3]) -> Vec<u8> {
+        let mut ret = format!("P6\n{} {}\n255\n", self.width(), self.height()).into_bytes();
+        for value in self.iter() {
+            ret.extend_from_slice(&f(value));
+        }
+        ret
+    }
+
+    /// Encodes the array as a binary PGM image, with one pixel per cell shaded by `f`.
+    pub fn to_pgm(&self, f: impl Fn(&T) -> u8) -> Vec<u8> {
+        let mut ret = format!("P5\n{} {}\n255\n", self.width(), self.height()).into_bytes();
+        ret.extend(self.iter().map(f));
+        ret
+    }
+
+    pub fn save_ppm(&self, path: &Path, f: impl Fn(&T) -> [u8; 3]) -> io::Result<()> {
+        fs::File::create(path)?.write_all(&self.to_ppm(f))
+    }
+
+    pub fn save_pgm(&self, path: &Path, f: impl Fn(&T) -> u8) -> io::Result<()> {
+        fs::File::create(path)?.write_all(&self.to_pgm(f))
+    }
+}
+
+/// Writes the steps of a simulation as numbered images, `name_00000.ppm` and so on, which can be
+/// put together into an animation afterwards.
+///
+/// The first error stops writing and is returned by `finish`, so that simulations don't have to
+/// handle errors on every step.
+pub struct Frames {
+    dir: PathBuf,
+    name: String,
+    count: usize,
+    error: Option<io::Error>,
+}
+
+impl Frames {
+    /// Creates `dir` if needed and starts numbering frames from 0.
+    pub fn new(dir: impl Into<PathBuf>, name: &str) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            name: name.to_string(),
+            count: 0,
+            error: None,
+        })
+    }
+
+    /// Creates a sink in the directory given by `AOC_FRAMES`, or `None` if it isn't set.
+    pub fn from_env(name: &str) -> io::Result<Option<Self>> {
+        std::env::var_os(FRAMES_VAR)
+            .map(|dir| Self::new(dir, name))
+            .transpose()
+    }
+
+    pub fn write_ppm<T: Default + Clone>(&mut self, grid: &Array2D<T>, f: impl Fn(&T) -> [u8; 3]) {
+        self.write("ppm", |path| grid.save_ppm(path, f));
+    }
+
+    pub fn write_pgm<T: Default + Clone>(&mut self, grid: &Array2D<T>, f: impl Fn(&T) -> u8) {
+        self.write("pgm", |path| grid.save_pgm(path, f));
+    }
+
+    /// The number of frames written, or the first error.
+    pub fn finish(self) -> io::Result<usize> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(self.count),
+        }
+    }
+
+    fn write(&mut self, extension: &str, save: impl FnOnce(&Path) -> io::Result<()>) {
+        if self.error.is_some() {
+            return;
+        }
+        let file = format!("{}_{:05}.{}", self.name, self.count, extension);
+        match save(&self.dir.join(file)) {
+            Ok(()) => self.count += 1,
+            Err(err) => self.error = Some(err),
+        }
+    }
+}
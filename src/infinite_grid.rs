@@ -0,0 +1,121 @@
+#![cfg_attr(not(test), allow(dead_code))]
+
+use crate::{
+    array2d::Array2D,
+    rect::Rect,
+    vec2::{vec2, Vec2i32},
+};
+
+/// A 2D grid without fixed bounds. Cells that were never written hold a default value, and the
+/// storage grows in any direction when writing outside of it.
+#[derive(Clone)]
+pub struct InfiniteGrid<T: Default + Clone> {
+    /// The position of the first cell of `cells`.
+    origin: Vec2i32,
+    cells: Array2D<T>,
+    /// The positions that were written to.
+    bounds: Option<Rect<i32>>,
+    default: T,
+}
+
+impl<T: Default + Clone> InfiniteGrid<T> {
+    pub fn new() -> Self {
+        Self::with_default(T::default())
+    }
+
+    pub fn with_default(default: T) -> Self {
+        Self {
+            origin: vec2(0, 0),
+            cells: Array2D::new(0, 0),
+            bounds: None,
+            default,
+        }
+    }
+
+    /// The smallest box around the positions that were written to, or `None` if the grid is
+    /// empty.
+    pub fn bounds(&self) -> Option<Rect<i32>> {
+        self.bounds
+    }
+
+    pub fn width(&self) -> usize {
+        self.bounds.map_or(0, |rect| rect.size().x as usize)
+    }
+
+    pub fn height(&self) -> usize {
+        self.bounds.map_or(0, |rect| rect.size().y as usize)
+    }
+
+    pub fn at(&self, pos: Vec2i32) -> &T {
+        self.cells.get(pos - self.origin).unwrap_or(&self.default)
+    }
+
+    pub fn at_mut(&mut self, pos: Vec2i32) -> &mut T {
+        self.grow(pos);
+        let point = Rect::new(pos, pos);
+        self.bounds = Some(self.bounds.map_or(point, |rect| rect.union(&point)));
+        self.cells.at_mut(pos - self.origin)
+    }
+
+    pub fn set(&mut self, pos: Vec2i32, value: T) {
+        *self.at_mut(pos) = value;
+    }
+
+    /// Iterates over all positions within the bounds, row by row, along with their values.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2i32, &T)> + '_ {
+        self.bounds
+            .iter()
+            .flat_map(|rect| rect.points())
+            .map(|pos| (pos, self.at(pos)))
+    }
+
+    /// Copies the cells within the bounds into an `Array2D`, whose origin is the smallest position.
+    pub fn to_array2d(&self) -> Array2D<T> {
+        let Some(rect) = self.bounds else {
+            return Array2D::new(0, 0);
+        };
+        let mut ret = Array2D::new_covering(&rect);
+        for (pos, value) in self.iter() {
+            ret.set(pos - rect.min, value.clone());
+        }
+        ret
+    }
+
+    /// Makes sure that `pos` lies within the storage, reallocating it with some room to spare.
+    fn grow(&mut self, pos: Vec2i32) {
+        if self.cells.in_bounds(pos - self.origin) {
+            return;
+        }
+
+        let size = vec2(self.cells.width() as i32, self.cells.height() as i32);
+        let (min, max) = if self.cells.width() == 0 || self.cells.height() == 0 {
+            (pos, pos)
+        } else {
+            let last = self.origin + size - vec2(1, 1);
+            (self.origin.min(pos), last.max(pos))
+        };
+
+        let pad = size.max(vec2(4, 4));
+        let origin = min - pad;
+        let new_size = max - min + vec2(1, 1) + pad * 2;
+        let new_size = new_size.try_cast::<usize>().unwrap();
+        let mut cells = Array2D::new(new_size.x, new_size.y);
+        cells.fill(self.default.clone());
+
+        let ofs = self.origin - origin;
+        for y in 0..self.cells.height() {
+            let start = ofs.x as usize;
+            let row = &mut cells.slice_mut(y + ofs.y as usize)[start..start + size.x as usize];
+            row.clone_from_slice(self.cells.slice(y));
+        }
+
+        self.origin = origin;
+        self.cells = cells;
+    }
+}
+
+impl<T: Default + Clone> Default for InfiniteGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
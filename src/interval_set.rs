@@ -0,0 +1,148 @@
+#![cfg_attr(not(test), allow(dead_code))]
+
+use std::ops::Range;
+
+/// A set of integers, stored as sorted half-open ranges that neither overlap nor touch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: num::PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    /// The ranges of the set, in increasing order.
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The number of integers in the set. It is counted in `u128`, because a range of a signed
+    /// type can be longer than the type's maximum.
+    pub fn total_len(&self) -> u128 {
+        self.ranges.iter().map(range_len).sum()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(i).is_some_and(|r| r.start <= value)
+    }
+
+    /// Whether all of `range` is in the set.
+    pub fn contains_range(&self, range: &Range<T>) -> bool {
+        if range.is_empty() {
+            return true;
+        }
+        let i = self.ranges.partition_point(|r| r.end <= range.start);
+        self.ranges
+            .get(i)
+            .is_some_and(|r| r.start <= range.start && range.end <= r.end)
+    }
+
+    /// Adds `range`, merging it with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        let mut merged = range;
+        if first < last {
+            merged.start = merged.start.min(self.ranges[first].start);
+            merged.end = merged.end.max(self.ranges[last - 1].end);
+        }
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Takes `range` out of the set, splitting a range that it lies within.
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let first = self.ranges.partition_point(|r| r.end <= range.start);
+        let last = self.ranges.partition_point(|r| r.start < range.end);
+        if first >= last {
+            return;
+        }
+        let before = self.ranges[first].start..range.start;
+        let after = range.end..self.ranges[last - 1].end;
+        let kept = [before, after].into_iter().filter(|r| !r.is_empty());
+        self.ranges.splice(first..last, kept);
+    }
+
+    /// Takes all integers of `other` out of the set.
+    pub fn subtract(&mut self, other: &Self) {
+        for range in &other.ranges {
+            self.remove(range.clone());
+        }
+    }
+
+    /// The integers that are in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let range = a.start.max(b.start)..a.end.min(b.end);
+            if !range.is_empty() {
+                ranges.push(range);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    /// The integers within `bounds` that aren't in the set.
+    pub fn complement(&self, bounds: Range<T>) -> Self {
+        let mut ret = Self::from(bounds);
+        ret.subtract(self);
+        ret
+    }
+
+    /// Iterates over the ranges between neighbouring ranges of the set.
+    pub fn gaps(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.windows(2).map(|w| w[0].end..w[1].start)
+    }
+}
+
+/// The length of a non-empty range, which always fits `u128`.
+fn range_len<T: num::PrimInt>(range: &Range<T>) -> u128 {
+    match (range.start.to_i128(), range.end.to_i128()) {
+        (Some(start), Some(end)) => end.abs_diff(start),
+        // Only `u128` values don't fit `i128`, and their difference can't overflow.
+        _ => (range.end - range.start).to_u128().unwrap(),
+    }
+}
+
+impl<T: num::PrimInt> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: num::PrimInt> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        let mut ret = Self::new();
+        ret.insert(range);
+        ret
+    }
+}
+
+impl<T: num::PrimInt> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut ret = Self::new();
+        for range in iter {
+            ret.insert(range);
+        }
+        ret
+    }
+}
@@ -1,5 +1,11 @@
 mod array2d;
 mod array3d;
+mod arrayn;
+mod bench;
+mod bitgrid;
+mod boxn;
+mod cli;
+mod cuboid;
 mod day1;
 mod day10;
 mod day11;
@@ -26,35 +32,142 @@ mod day7;
 mod day8;
 mod day9;
 mod flatmap;
+mod heading;
+mod image;
+mod infinite_grid;
+mod interval_set;
+mod mesh;
+mod parse;
+mod rect;
+mod report;
+mod snapshot;
+mod solution;
+#[cfg(test)]
+mod tests;
 mod timer;
 mod vec2;
 mod vec3;
 mod vec4;
+mod vecn;
+
+use std::process::exit;
+
+use cli::{Args, BenchArgs};
+use report::Format;
+use solution::Day;
+use timer::Summary;
+
+/// Returns an identifier of the input to use for a day, along with its contents.
+fn read_input(args: &Args, solution: &dyn Day) -> (String, String) {
+    match args.input_path(solution.day()) {
+        None => (solution.input_name(), solution.input().to_string()),
+        Some(path) => {
+            let input = std::fs::read_to_string(&path).unwrap_or_else(|err| {
+                eprintln!("error: can't read {}: {}", path.display(), err);
+                exit(1);
+            });
+            (path.display().to_string(), input)
+        }
+    }
+}
+
+fn run_bench(args: &Args, bench_args: &BenchArgs) {
+    let baseline = bench_args.baseline.as_ref().map(|path| {
+        bench::load_baseline(path).unwrap_or_else(|err| {
+            eprintln!("error: {}", err);
+            exit(1);
+        })
+    });
+
+    let mut entries = Vec::new();
+    for day in args.days.clone() {
+        let solution = DAYS[day as usize - 1];
+        let (_, input) = read_input(args, solution);
+        let day_entries = bench::bench(
+            solution,
+            &input,
+            args.part,
+            bench_args.warmup,
+            bench_args.runs,
+        );
+        entries.extend(day_entries.unwrap_or_else(|err| {
+            eprintln!("error: {}", err);
+            exit(1);
+        }));
+    }
+    bench::print(&entries, baseline.as_deref());
+
+    if let Some(path) = &bench_args.save {
+        bench::save_baseline(path, &entries).unwrap_or_else(|err| {
+            eprintln!("error: {}", err);
+            exit(1);
+        });
+    }
+}
+
+const DAYS: [&dyn Day; cli::DAYS as usize] = [
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+    &day17::Day17,
+    &day18::Day18,
+    &day19::Day19,
+    &day20::Day20,
+    &day21::Day21,
+    &day22::Day22,
+    &day23::Day23,
+    &day24::Day24,
+    &day25::Day25,
+];
 
 fn main() {
-    day1::solve();
-    day2::solve();
-    day3::solve();
-    day4::solve();
-    day5::solve();
-    day6::solve();
-    day7::solve();
-    day8::solve();
-    day9::solve();
-    day10::solve();
-    day11::solve();
-    day12::solve();
-    day13::solve();
-    day14::solve();
-    day15::solve();
-    day16::solve();
-    day17::solve();
-    day18::solve();
-    day19::solve();
-    day20::solve();
-    day21::solve();
-    day22::solve();
-    day23::solve();
-    day24::solve();
-    day25::solve();
+    let args = Args::parse(std::env::args().skip(1)).unwrap_or_else(|err| {
+        if !err.is_empty() {
+            eprintln!("error: {}\n", err);
+        }
+        eprintln!("{}", cli::USAGE);
+        exit(2);
+    });
+
+    if let Some(bench_args) = &args.bench {
+        run_bench(&args, bench_args);
+        return;
+    }
+
+    let mut summary = Summary::default();
+    for day in args.days.clone() {
+        let solution = DAYS[day as usize - 1];
+        let (name, input) = read_input(&args, solution);
+        let timer = solution.run(&input, args.part).unwrap_or_else(|err| {
+            eprintln!("error: {}", err);
+            exit(1);
+        });
+        if args.format == Format::Text {
+            timer.print();
+        }
+        solution.export(&input).unwrap_or_else(|err| {
+            eprintln!("error: day {}: can't export debug output: {}", day, err);
+            exit(1);
+        });
+        summary.add(name, timer);
+    }
+
+    match args.format {
+        Format::Text => summary.print(),
+        Format::Json => print!("{}", report::to_json(&report::records(&summary))),
+        Format::Csv => print!("{}", report::to_csv(&report::records(&summary))),
+    }
 }
@@ -0,0 +1,191 @@
+#![cfg_attr(not(test), allow(dead_code))]
+
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::array3d::Array3D;
+
+/// The environment variable naming the directory that meshes are exported to.
+pub const MESH_VAR: &str = "AOC_MESH";
+
+/// An axis-aligned rectangle on the surface of a voxel shape.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Quad {
+    /// Counter-clockwise when looking at the quad from outside.
+    pub corners: [[i32; 3]; 4],
+    /// Unit vector pointing out of the shape.
+    pub normal: [i32; 3],
+}
+
+impl Quad {
+    pub fn area(&self) -> i64 {
+        let [a, b, _, d] = self.corners;
+        let side =
+            |p: [i32; 3], q: [i32; 3]| (0..3).map(|i| (p[i] - q[i]).abs() as i64).sum::<i64>();
+        side(a, b) * side(a, d)
+    }
+}
+
+/// The exposed surface of the solid cells of an `Array3D`, made of quads. Cell `(x, y, z)`
+/// covers the unit cube from `(x, y, z)` to `(x + 1, y + 1, z + 1)`.
+#[derive(Clone, Debug, Default)]
+pub struct Mesh {
+    pub quads: Vec<Quad>,
+}
+
+impl Mesh {
+    /// One quad per face between a solid cell and an empty one. Cells outside of the grid count
+    /// as empty.
+    pub fn new<T: Default + Clone>(grid: &Array3D<T>, solid: impl Fn(&T) -> bool) -> Self {
+        Self::build(grid, solid, false)
+    }
+
+    /// Like `new`, but merges neighbouring faces in the same plane into larger rectangles.
+    pub fn greedy<T: Default + Clone>(grid: &Array3D<T>, solid: impl Fn(&T) -> bool) -> Self {
+        Self::build(grid, solid, true)
+    }
+
+    /// The total area of the surface, which is the number of exposed cell faces.
+    pub fn area(&self) -> i64 {
+        self.quads.iter().map(|quad| quad.area()).sum()
+    }
+
+    fn build<T: Default + Clone>(
+        grid: &Array3D<T>,
+        solid: impl Fn(&T) -> bool,
+        merge: bool,
+    ) -> Self {
+        let shape = grid.shape();
+        let is_solid = |pos: [i32; 3]| grid.get(pos).is_some_and(&solid);
+        let mut quads = Vec::new();
+
+        for axis in 0..3 {
+            // u, v and the axis form a right-handed system
+            let u = (axis + 1) % 3;
+            let v = (axis + 2) % 3;
+            let (size_u, size_v) = (shape[u], shape[v]);
+
+            for dir in [-1, 1] {
+                for layer in 0..shape[axis] as i32 {
+                    let cell = |i: usize, j: usize| {
+                        let mut pos = [0; 3];
+                        pos[axis] = layer;
+                        pos[u] = i as i32;
+                        pos[v] = j as i32;
+                        pos
+                    };
+                    let mut exposed = vec![false; size_u * size_v];
+                    for j in 0..size_v {
+                        for i in 0..size_u {
+                            let pos = cell(i, j);
+                            let mut next = pos;
+                            next[axis] += dir;
+                            exposed[j * size_u + i] = is_solid(pos) && !is_solid(next);
+                        }
+                    }
+
+                    for j in 0..size_v {
+                        for i in 0..size_u {
+                            if !exposed[j * size_u + i] {
+                                continue;
+                            }
+                            let mut w = 1;
+                            while merge && i + w < size_u && exposed[j * size_u + i + w] {
+                                w += 1;
+                            }
+                            let mut h = 1;
+                            while merge
+                                && j + h < size_v
+                                && (i..i + w).all(|k| exposed[(j + h) * size_u + k])
+                            {
+                                h += 1;
+                            }
+                            for jj in j..j + h {
+                                for k in i..i + w {
+                                    exposed[jj * size_u + k] = false;
+                                }
+                            }
+
+                            let plane = if dir > 0 { layer + 1 } else { layer };
+                            let corner = |i: usize, j: usize| {
+                                let mut pos = cell(i, j);
+                                pos[axis] = plane;
+                                pos
+                            };
+                            let mut corners = [
+                                corner(i, j),
+                                corner(i + w, j),
+                                corner(i + w, j + h),
+                                corner(i, j + h),
+                            ];
+                            if dir < 0 {
+                                corners.reverse();
+                            }
+                            let mut normal = [0; 3];
+                            normal[axis] = dir;
+                            quads.push(Quad { corners, normal });
+                        }
+                    }
+                }
+            }
+        }
+
+        Self { quads }
+    }
+
+    /// Wavefront OBJ text with shared vertices and one quad face per quad.
+    pub fn to_obj(&self) -> String {
+        let mut vertices = HashMap::new();
+        let mut ret = String::new();
+        let mut faces = String::new();
+        for quad in &self.quads {
+            faces += "f";
+            for corner in quad.corners {
+                let next = vertices.len() + 1;
+                let index = *vertices.entry(corner).or_insert_with(|| {
+                    let _ = writeln!(ret, "v {} {} {}", corner[0], corner[1], corner[2]);
+                    next
+                });
+                let _ = write!(faces, " {}", index);
+            }
+            faces += "\n";
+        }
+        ret + &faces
+    }
+
+    /// ASCII STL text, with every quad split into two triangles.
+    pub fn to_stl(&self, name: &str) -> String {
+        let mut ret = format!("solid {}\n", name);
+        for quad in &self.quads {
+            let [a, b, c, d] = quad.corners;
+            for triangle in [[a, b, c], [a, c, d]] {
+                let [x, y, z] = quad.normal;
+                let _ = writeln!(ret, "  facet normal {} {} {}", x, y, z);
+                ret += "    outer loop\n";
+                for [x, y, z] in triangle {
+                    let _ = writeln!(ret, "      vertex {} {} {}", x, y, z);
+                }
+                ret += "    endloop\n";
+                ret += "  endfacet\n";
+            }
+        }
+        let _ = writeln!(ret, "endsolid {}", name);
+        ret
+    }
+
+    /// Writes `name.obj` and `name.stl` to `dir`.
+    pub fn save(&self, dir: &Path, name: &str) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join(format!("{}.obj", name)), self.to_obj())?;
+        fs::write(dir.join(format!("{}.stl", name)), self.to_stl(name))
+    }
+}
+
+/// The directory given by `AOC_MESH`, if it is set.
+pub fn env_dir() -> Option<PathBuf> {
+    std::env::var_os(MESH_VAR).map(PathBuf::from)
+}
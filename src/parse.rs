@@ -0,0 +1,268 @@
+#![cfg_attr(not(test), allow(dead_code))]
+
+use std::{fmt, str::FromStr};
+
+use crate::array2d::{Array2D, Markers};
+
+/// An error in a puzzle input, pointing at the offending token.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The day whose input failed to parse, 0 if not known yet.
+    pub day: u32,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, in characters.
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+    /// The full line the error is on.
+    pub text: String,
+}
+
+impl ParseError {
+    /// Creates an error at `token`, which must be a slice of `input`.
+    pub fn at(input: &str, token: &str, expected: impl Into<String>) -> Self {
+        let offset = (token.as_ptr() as usize)
+            .saturating_sub(input.as_ptr() as usize)
+            .min(input.len());
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        let found = token.lines().next().unwrap_or("");
+        Self {
+            day: 0,
+            line: before.matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            expected: expected.into(),
+            found: found.chars().take(32).collect(),
+            text: input[line_start..line_end].trim_end().to_string(),
+        }
+    }
+
+    /// Creates an error right after `token`, e.g. for a missing field at the end of a line.
+    pub fn after(input: &str, token: &str, expected: impl Into<String>) -> Self {
+        Self::at(input, &token[token.len()..], expected)
+    }
+
+    pub fn in_day(mut self, day: u32) -> Self {
+        self.day = day;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.day != 0 {
+            write!(f, "day {}, ", self.day)?;
+        }
+        write!(
+            f,
+            "line {}, column {}: expected {}, ",
+            self.line, self.column, self.expected
+        )?;
+        if self.found.is_empty() {
+            writeln!(f, "found nothing")?;
+        } else {
+            writeln!(f, "found '{}'", self.found)?;
+        }
+        writeln!(f, "  | {}", self.text)?;
+        write!(f, "  | {:>1$}", "^", self.column)
+    }
+}
+
+/// Parses `token`, a slice of `input`, as a number.
+pub fn number<T: FromStr>(input: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at(input, token, "number"))
+}
+
+/// Parses each of `tokens`, which are slices of `input`, as a number.
+pub fn numbers<T: FromStr, const N: usize>(
+    input: &str,
+    tokens: [&str; N],
+) -> Result<[T; N], ParseError> {
+    let values = tokens
+        .iter()
+        .map(|token| number(input, token))
+        .collect::<Result<Vec<T>, _>>()?;
+    Ok(values.try_into().unwrap_or_else(|_| unreachable!()))
+}
+
+/// Strips `prefix` from `s`, a slice of `input`.
+pub fn prefix<'a>(input: &str, s: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+    s.strip_prefix(prefix)
+        .ok_or_else(|| ParseError::at(input, s, format!("'{}'", prefix)))
+}
+
+/// Splits `s`, a slice of `input`, around the first `separator`.
+pub fn split_once<'a>(
+    input: &str,
+    s: &'a str,
+    separator: &str,
+) -> Result<(&'a str, &'a str), ParseError> {
+    s.split_once(separator).ok_or_else(|| {
+        let expected = format!("a value followed by '{}'", separator);
+        ParseError::at(input, s, expected)
+    })
+}
+
+/// Takes the next item of `parts`, which are slices of `line`, itself a slice of `input`.
+pub fn next<'a>(
+    input: &str,
+    line: &'a str,
+    parts: &mut impl Iterator<Item = &'a str>,
+    expected: &str,
+) -> Result<&'a str, ParseError> {
+    parts
+        .next()
+        .ok_or_else(|| ParseError::after(input, line, expected))
+}
+
+/// Iterates over the non-blank lines of `s`, with surrounding whitespace trimmed.
+pub fn lines(s: &str) -> impl Iterator<Item = &str> {
+    s.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+}
+
+/// Splits `s`, a slice of `input`, into the rows of a map, with trailing whitespace trimmed.
+/// Blank lines at the end are dropped, while blank lines between rows are an error.
+pub fn map_rows<'a>(input: &str, s: &'a str) -> Result<Vec<&'a str>, ParseError> {
+    let mut rows: Vec<&str> = s.lines().map(|line| line.trim_end()).collect();
+    while rows.last().is_some_and(|row| row.is_empty()) {
+        rows.pop();
+    }
+    match rows.iter().find(|row| row.is_empty()) {
+        Some(blank) => Err(ParseError::at(input, blank, "a row of the map")),
+        None => Ok(rows),
+    }
+}
+
+/// Iterates over the paragraphs of `s`, which are separated by blank lines. Leading whitespace
+/// of the first line of a paragraph is kept.
+pub fn paragraphs(s: &str) -> impl Iterator<Item = &str> {
+    let mut offset = 0;
+    std::iter::from_fn(move || {
+        let mut start = None;
+        let mut end = offset;
+        for line in s[offset..].split_inclusive('\n') {
+            let line_start = offset;
+            offset += line.len();
+            if line.trim().is_empty() {
+                if start.is_some() {
+                    break;
+                }
+            } else {
+                start.get_or_insert(line_start);
+                end = line_start + line.trim_end().len();
+            }
+        }
+        start.map(|start| &s[start..end])
+    })
+}
+
+/// Iterates over the integers in `s`. A '-' right before the digits is taken as a sign, unless it
+/// follows a letter or digit, so that ranges like `2-4` are two positive numbers.
+pub fn integer_tokens(s: &str) -> impl Iterator<Item = &str> {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    std::iter::from_fn(move || {
+        while i < bytes.len() {
+            let start = i;
+            let sign = bytes[i] == b'-'
+                && bytes.get(i + 1).is_some_and(|b| b.is_ascii_digit())
+                && (i == 0 || !bytes[i - 1].is_ascii_alphanumeric());
+            i += 1;
+            if sign || bytes[start].is_ascii_digit() {
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+                return Some(&s[start..i]);
+            }
+        }
+        None
+    })
+}
+
+/// Extracts all integers in `s`, a slice of `input`, ignoring any text around them.
+pub fn integers<T: FromStr>(input: &str, s: &str) -> Result<Vec<T>, ParseError> {
+    integer_tokens(s)
+        .map(|token| number(input, token))
+        .collect()
+}
+
+/// Extracts exactly `N` integers from `s`, a slice of `input`.
+pub fn integers_n<T: FromStr, const N: usize>(input: &str, s: &str) -> Result<[T; N], ParseError> {
+    let mut tokens = integer_tokens(s);
+    let mut fields = [""; N];
+    for field in &mut fields {
+        *field = tokens
+            .next()
+            .ok_or_else(|| ParseError::after(input, s, format!("{} numbers", N)))?;
+    }
+    if let Some(extra) = tokens.next() {
+        return Err(ParseError::at(input, extra, format!("only {} numbers", N)));
+    }
+    numbers(input, fields)
+}
+
+/// Matches `s`, a slice of `input`, against a pattern in which every `{}` captures a field,
+/// e.g. `"move {} from {} to {}"`. The text between the fields has to match exactly.
+pub fn pattern<'a, const N: usize>(
+    input: &str,
+    s: &'a str,
+    pattern: &str,
+) -> Result<[&'a str; N], ParseError> {
+    let literals: Vec<&str> = pattern.split("{}").collect();
+    assert!(literals.len() == N + 1, "pattern needs {} fields", N);
+
+    let mut rest = prefix(input, s, literals[0])?;
+    let mut fields = [""; N];
+    for i in 0..N {
+        let literal = literals[i + 1];
+        let (field, after) = if literal.is_empty() {
+            assert!(i + 1 == N, "fields in a pattern need a separator");
+            rest.split_at(rest.len())
+        } else {
+            split_once(input, rest, literal)?
+        };
+        fields[i] = field;
+        rest = after;
+    }
+
+    if rest.is_empty() {
+        Ok(fields)
+    } else {
+        Err(ParseError::at(input, rest, "end of line"))
+    }
+}
+
+/// Parses the lines of `s`, a slice of `input`, into a grid like `Array2D::from_str_map`, except
+/// that all lines have to be of the same length.
+pub fn grid<T: Default + Clone>(
+    input: &str,
+    s: &str,
+    expected: &str,
+    markers: &str,
+    f: impl Fn(char) -> Option<T>,
+) -> Result<(Array2D<T>, Markers), ParseError> {
+    let rows = map_rows(input, s)?;
+    if rows.is_empty() {
+        return Err(ParseError::at(input, s, "a grid"));
+    }
+
+    let width = rows[0].chars().count();
+    if let Some(row) = rows.iter().find(|row| row.chars().count() != width) {
+        let expected = format!("a row of {} cells", width);
+        return Err(ParseError::at(input, row, expected));
+    }
+
+    Array2D::from_str_map(input, s, markers, f).map_err(|err| ParseError {
+        expected: expected.to_string(),
+        ..err
+    })
+}
@@ -0,0 +1,5 @@
+#![cfg_attr(not(test), allow(dead_code))]
+
+use crate::{boxn::box_type, vec2::Vec2};
+
+box_type!(Rect, Vec2, area, x, y);
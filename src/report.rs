@@ -0,0 +1,120 @@
+use std::{fmt::Write, str::FromStr};
+
+use crate::timer::{millis, Summary};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => Err(format!(
+                "invalid format '{}', expected text, json or csv",
+                s
+            )),
+        }
+    }
+}
+
+/// One part of a day, as emitted by the machine-readable formats.
+pub struct Record<'a> {
+    pub day: u32,
+    pub part: u32,
+    /// `None` for parts without an answer, which become `null` in JSON and an empty field in CSV.
+    pub answer: Option<&'a str>,
+    pub parse_ms: f64,
+    pub duration_ms: f64,
+    pub input: &'a str,
+}
+
+pub fn records(summary: &Summary) -> Vec<Record<'_>> {
+    let mut records = Vec::new();
+    for run in summary.runs() {
+        for part in [1, 2] {
+            if let Some(result) = run.timer.result(part) {
+                records.push(Record {
+                    day: run.timer.day(),
+                    part,
+                    answer: result.answer.as_deref(),
+                    parse_ms: millis(run.timer.parse_duration()),
+                    duration_ms: millis(result.duration),
+                    input: &run.input,
+                });
+            }
+        }
+    }
+    records
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            '\n' => out += "\\n",
+            '\r' => out += "\\r",
+            '\t' => out += "\\t",
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+pub fn to_json(records: &[Record]) -> String {
+    let mut out = String::from("[\n");
+    for (i, record) in records.iter().enumerate() {
+        let separator = if i + 1 < records.len() { "," } else { "" };
+        writeln!(
+            out,
+            "  {{\"day\": {}, \"part\": {}, \"answer\": {}, \"parse_ms\": {:.3}, \"duration_ms\": {:.3}, \"input\": {}}}{}",
+            record.day,
+            record.part,
+            record.answer.map_or(String::from("null"), json_string),
+            record.parse_ms,
+            record.duration_ms,
+            json_string(record.input),
+            separator
+        )
+        .unwrap();
+    }
+    out += "]\n";
+    out
+}
+
+pub fn to_csv(records: &[Record]) -> String {
+    let mut out = String::from("day,part,answer,parse_ms,duration_ms,input\n");
+    for record in records {
+        writeln!(
+            out,
+            "{},{},{},{:.3},{:.3},{}",
+            record.day,
+            record.part,
+            record.answer.map_or(String::new(), csv_field),
+            record.parse_ms,
+            record.duration_ms,
+            csv_field(record.input)
+        )
+        .unwrap();
+    }
+    out
+}
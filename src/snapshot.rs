@@ -0,0 +1,174 @@
+#![cfg_attr(not(test), allow(dead_code))]
+
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+#[cfg(target_endian = "little")]
+use bytemuck::Pod;
+
+#[cfg(target_endian = "little")]
+use crate::arrayn::ArrayN;
+use crate::bitgrid::BitGrid;
+
+/// The environment variable naming the directory that simulations write snapshots to.
+pub const SNAPSHOT_VAR: &str = "AOC_SNAPSHOTS";
+
+#[cfg(target_endian = "little")]
+const ARRAY_MAGIC: &[u8; 4] = b"AOCA";
+const BITGRID_MAGIC: &[u8; 4] = b"AOCB";
+
+// A snapshot starts with a header of little endian numbers: the magic, the number of
+// dimensions (u32), the size of an element in bytes (u32), the size along each dimension (u64
+// each) and the FNV-1a hash of the data (u64). The data follows in little endian as well: the
+// words of a bit grid are converted, while array elements are written as they are laid out in
+// memory, which is why arrays only support snapshots on little endian targets.
+
+fn encode(magic: &[u8; 4], shape: &[usize], elem_size: usize, data: &[u8]) -> Vec<u8> {
+    let mut ret = magic.to_vec();
+    ret.extend_from_slice(&(shape.len() as u32).to_le_bytes());
+    ret.extend_from_slice(&(elem_size as u32).to_le_bytes());
+    for size in shape {
+        ret.extend_from_slice(&(*size as u64).to_le_bytes());
+    }
+    ret.extend_from_slice(&checksum(data).to_le_bytes());
+    ret.extend_from_slice(data);
+    ret
+}
+
+/// Checks the header of `bytes` and returns the shape and the data.
+fn decode<'a>(
+    magic: &[u8; 4],
+    bytes: &'a [u8],
+    dims: usize,
+    elem_size: usize,
+) -> io::Result<(Vec<usize>, &'a [u8])> {
+    let mut rest = bytes;
+    let mut take = |len: usize| {
+        if rest.len() < len {
+            return Err(invalid("truncated snapshot header"));
+        }
+        let (head, tail) = rest.split_at(len);
+        rest = tail;
+        Ok(head)
+    };
+    let u32_at = |b: &[u8]| u32::from_le_bytes(b.try_into().unwrap()) as usize;
+    let u64_at = |b: &[u8]| u64::from_le_bytes(b.try_into().unwrap());
+
+    if take(4)? != magic {
+        return Err(invalid("not a snapshot of this kind"));
+    }
+    if u32_at(take(4)?) != dims {
+        return Err(invalid(format!(
+            "expected a snapshot with {} dimensions",
+            dims
+        )));
+    }
+    if u32_at(take(4)?) != elem_size {
+        return Err(invalid(format!("expected elements of {} bytes", elem_size)));
+    }
+    let shape = (0..dims)
+        .map(|_| Ok(u64_at(take(8)?) as usize))
+        .collect::<io::Result<Vec<_>>>()?;
+    let expected = u64_at(take(8)?);
+    if checksum(rest) != expected {
+        return Err(invalid("snapshot checksum mismatch"));
+    }
+    Ok((shape, rest))
+}
+
+/// Makes sure that `data` holds exactly the elements of `shape`, before anything is allocated
+/// for them.
+fn check_len(shape: &[usize], elem_size: usize, data: &[u8]) -> io::Result<()> {
+    let len = shape
+        .iter()
+        .try_fold(elem_size, |len, size| len.checked_mul(*size));
+    if len != Some(data.len()) {
+        return Err(invalid("snapshot data doesn't match its shape"));
+    }
+    Ok(())
+}
+
+fn checksum(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn invalid(message: impl Into<String>) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message.into())
+}
+
+#[cfg(target_endian = "little")]
+impl<T: Pod + Default, const D: usize> ArrayN<T, D> {
+    /// Encodes the array as a binary snapshot.
+    pub fn to_snapshot(&self) -> Vec<u8> {
+        let data = bytemuck::cast_slice(&self.data);
+        encode(ARRAY_MAGIC, &self.shape, size_of::<T>(), data)
+    }
+
+    /// Decodes a snapshot written by `to_snapshot` for the same element type.
+    pub fn from_snapshot(bytes: &[u8]) -> io::Result<Self> {
+        let (shape, data) = decode(ARRAY_MAGIC, bytes, D, size_of::<T>())?;
+        check_len(&shape, size_of::<T>(), data)?;
+        let mut ret = Self::from_shape(shape.try_into().unwrap());
+        bytemuck::cast_slice_mut(&mut ret.data).copy_from_slice(data);
+        Ok(ret)
+    }
+
+    pub fn save_snapshot(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_snapshot())
+    }
+
+    pub fn load_snapshot(path: &Path) -> io::Result<Self> {
+        Self::from_snapshot(&fs::read(path)?)
+    }
+}
+
+impl BitGrid {
+    /// Encodes the grid as a binary snapshot of its words, row by row.
+    pub fn to_snapshot(&self) -> Vec<u8> {
+        let data: Vec<u8> = self.words().iter().flat_map(|w| w.to_le_bytes()).collect();
+        encode(BITGRID_MAGIC, &[self.width(), self.height()], 8, &data)
+    }
+
+    /// Decodes a snapshot written by `to_snapshot`. Rejects rows with bits set past the width.
+    pub fn from_snapshot(bytes: &[u8]) -> io::Result<Self> {
+        let (shape, data) = decode(BITGRID_MAGIC, bytes, 2, 8)?;
+        let (width, height) = (shape[0], shape[1]);
+        let words_per_row = width.div_ceil(64);
+        check_len(&[words_per_row, height], 8, data)?;
+
+        let words: Vec<u64> = data
+            .chunks_exact(8)
+            .map(|b| u64::from_le_bytes(b.try_into().unwrap()))
+            .collect();
+        let padding = width % 64;
+        if padding != 0
+            && words
+                .chunks_exact(words_per_row)
+                .any(|row| row[words_per_row - 1] >> padding != 0)
+        {
+            return Err(invalid("snapshot has bits set past the width"));
+        }
+
+        let mut ret = Self::new(width, height);
+        ret.words_mut().copy_from_slice(&words);
+        Ok(ret)
+    }
+
+    pub fn save_snapshot(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_snapshot())
+    }
+
+    pub fn load_snapshot(path: &Path) -> io::Result<Self> {
+        Self::from_snapshot(&fs::read(path)?)
+    }
+}
+
+/// The directory given by `AOC_SNAPSHOTS`, if it is set.
+pub fn env_dir() -> Option<PathBuf> {
+    std::env::var_os(SNAPSHOT_VAR).map(PathBuf::from)
+}
@@ -0,0 +1,104 @@
+use std::io;
+
+use crate::{parse::ParseError, timer::Timer};
+
+/// A puzzle solution, split into parsing the input and solving both parts on it.
+pub trait Solution {
+    const DAY: u32;
+    const INPUT: &'static str;
+
+    type Input;
+    type Answer1: Answer;
+    type Answer2: Answer;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    fn part2(input: &Self::Input) -> Self::Answer2;
+
+    /// Writes debug output that the environment asks for, such as the frames of a simulation.
+    /// The runner calls this after the timed parts.
+    fn export(_input: &Self::Input) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A value that a part can return as its answer.
+pub trait Answer {
+    /// The answer as text, or `None` if the part found no answer.
+    fn answer(&self) -> Option<String>;
+}
+
+macro_rules! display_answer {
+    ($($ty:ty),+) => {
+        $(
+            impl Answer for $ty {
+                fn answer(&self) -> Option<String> {
+                    Some(self.to_string())
+                }
+            }
+        )+
+    };
+}
+
+display_answer!(i32, i64, u32, u64, u128, usize, String);
+
+impl<T: Answer> Answer for Option<T> {
+    fn answer(&self) -> Option<String> {
+        self.as_ref().and_then(Answer::answer)
+    }
+}
+
+/// Placeholder answer for parts that aren't solved.
+pub struct Unsolved;
+
+impl Answer for Unsolved {
+    fn answer(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Object-safe view of a `Solution`, used by the registry.
+pub trait Day {
+    fn day(&self) -> u32;
+    fn input(&self) -> &'static str;
+    /// Where the bundled input comes from, relative to the repository root.
+    fn input_name(&self) -> String;
+    fn run(&self, input: &str, part: Option<u32>) -> Result<Timer, ParseError>;
+    /// Parses `input` again and exports debug output for it, doing nothing if it doesn't parse.
+    fn export(&self, input: &str) -> io::Result<()>;
+}
+
+impl<S: Solution> Day for S {
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn input(&self) -> &'static str {
+        S::INPUT
+    }
+
+    fn input_name(&self) -> String {
+        format!("src/inputs/{}.txt", S::DAY)
+    }
+
+    fn run(&self, input: &str, part: Option<u32>) -> Result<Timer, ParseError> {
+        let mut timer = Timer::new(S::DAY);
+        let input = timer
+            .parse(|| S::parse(input))
+            .map_err(|err| err.in_day(S::DAY))?;
+        if part != Some(2) {
+            timer.part1(|| S::part1(&input));
+        }
+        if part != Some(1) {
+            timer.part2(|| S::part2(&input));
+        }
+        Ok(timer)
+    }
+
+    fn export(&self, input: &str) -> io::Result<()> {
+        match S::parse(input) {
+            Ok(input) => S::export(&input),
+            Err(_) => Ok(()),
+        }
+    }
+}
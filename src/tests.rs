@@ -0,0 +1,1170 @@
+use std::time::Duration;
+
+use crate::{
+    array2d::Array2D,
+    array3d::Array3D,
+    arrayn::ArrayN,
+    bench::{from_baseline, to_baseline, Entry, Stage, Stats},
+    bitgrid::BitGrid,
+    cuboid::Cuboid,
+    day1::Day1,
+    day10::Day10,
+    day11::Day11,
+    day12::Day12,
+    day13::Day13,
+    day14::Day14,
+    day15,
+    day15::Day15,
+    day16::Day16,
+    day17::Day17,
+    day18::Day18,
+    day19::Day19,
+    day2::Day2,
+    day20::Day20,
+    day21::Day21,
+    day22,
+    day22::Day22,
+    day23::Day23,
+    day24::Day24,
+    day25::Day25,
+    day3::Day3,
+    day4::Day4,
+    day5::Day5,
+    day6::Day6,
+    day7::Day7,
+    day8::Day8,
+    day9::Day9,
+    flatmap::FlatMap,
+    heading::{Compass, Heading},
+    image::Frames,
+    infinite_grid::InfiniteGrid,
+    interval_set::IntervalSet,
+    mesh::Mesh,
+    parse,
+    rect::Rect,
+    report::{to_csv, to_json, Record},
+    solution::Solution,
+    vec2::{vec2, Vec2i32, Vec2i64, Vec2u64, Vec2usize},
+    vec3::{vec3, Vec3i32, Vec3u32, Vec3u64, Vec3usize},
+    vec4::{vec4, Vec4, Vec4i32, Vec4i64, Vec4u64, Vec4usize},
+};
+
+/// Renders the CRT output of day 10 with one character per pixel.
+fn crt(screen: &str) -> String {
+    let lines: Vec<String> = screen
+        .lines()
+        .map(|line| line.chars().step_by(2).collect())
+        .collect();
+    lines.join("\n")
+}
+
+#[test]
+fn day01() {
+    let input = Day1::parse(Day1::INPUT).unwrap();
+    assert_eq!(Day1::part1(&input), 66616);
+    assert_eq!(Day1::part2(&input), 199172);
+}
+
+#[test]
+fn day02() {
+    let input = Day2::parse(Day2::INPUT).unwrap();
+    assert_eq!(Day2::part1(&input), 11841);
+    assert_eq!(Day2::part2(&input), 13022);
+}
+
+#[test]
+fn day03() {
+    let input = Day3::parse(Day3::INPUT).unwrap();
+    assert_eq!(Day3::part1(&input), 7831);
+    assert_eq!(Day3::part2(&input), 2683);
+}
+
+#[test]
+fn day04() {
+    let input = Day4::parse(Day4::INPUT).unwrap();
+    assert_eq!(Day4::part1(&input), 651);
+    assert_eq!(Day4::part2(&input), 956);
+}
+
+#[test]
+fn day05_example() {
+    let input = Day5::parse(include_str!("inputs/5_1.txt")).unwrap();
+    assert_eq!(Day5::part1(&input), "CMZ");
+    assert_eq!(Day5::part2(&input), "MCD");
+}
+
+#[test]
+fn day05() {
+    let input = Day5::parse(Day5::INPUT).unwrap();
+    assert_eq!(Day5::part1(&input), "JCMHLVGMG");
+    assert_eq!(Day5::part2(&input), "LVMRWSSPZ");
+}
+
+#[test]
+fn day06() {
+    let input = Day6::parse(Day6::INPUT).unwrap();
+    assert_eq!(Day6::part1(&input), Some(1093));
+    assert_eq!(Day6::part2(&input), Some(3534));
+}
+
+#[test]
+fn day06_short() {
+    let input = Day6::parse("abcabcdabc\n").unwrap();
+    assert_eq!(Day6::part1(&input), Some(7));
+    assert_eq!(Day6::part2(&input), None);
+    assert_eq!(Day6::part1(&Day6::parse("").unwrap()), None);
+}
+
+#[test]
+fn day07_example() {
+    let input = Day7::parse(include_str!("inputs/7_1.txt")).unwrap();
+    assert_eq!(Day7::part1(&input), 95437);
+    assert_eq!(Day7::part2(&input), 24933642);
+}
+
+#[test]
+fn day07() {
+    let input = Day7::parse(Day7::INPUT).unwrap();
+    assert_eq!(Day7::part1(&input), 1770595);
+    assert_eq!(Day7::part2(&input), 2195372);
+}
+
+#[test]
+fn day08() {
+    let input = Day8::parse(Day8::INPUT).unwrap();
+    assert_eq!(Day8::part1(&input), 1825);
+    assert_eq!(Day8::part2(&input), 235200);
+}
+
+#[test]
+fn day09() {
+    let input = Day9::parse(Day9::INPUT).unwrap();
+    assert_eq!(Day9::part1(&input), 6522);
+    assert_eq!(Day9::part2(&input), 2717);
+}
+
+#[test]
+fn day10_example() {
+    let input = Day10::parse(include_str!("inputs/10_1.txt")).unwrap();
+    assert_eq!(Day10::part1(&input), 13140);
+    assert_eq!(
+        crt(&Day10::part2(&input)),
+        "##..##..##..##..##..##..##..##..##..##..\n\
+         ###...###...###...###...###...###...###.\n\
+         ####....####....####....####....####....\n\
+         #####.....#####.....#####.....#####.....\n\
+         ######......######......######......####\n\
+         #######.......#######.......#######....."
+    );
+}
+
+#[test]
+fn day10() {
+    let input = Day10::parse(Day10::INPUT).unwrap();
+    assert_eq!(Day10::part1(&input), 14820);
+    assert_eq!(
+        crt(&Day10::part2(&input)),
+        "###..####.####.#..#.####.####.#..#..##..\n\
+         #..#....#.#....#.#..#....#....#..#.#..#.\n\
+         #..#...#..###..##...###..###..####.#..#.\n\
+         ###...#...#....#.#..#....#....#..#.####.\n\
+         #.#..#....#....#.#..#....#....#..#.#..#.\n\
+         #..#.####.####.#..#.####.#....#..#.#..#."
+    );
+}
+
+#[test]
+fn day11_example() {
+    let input = Day11::parse(include_str!("inputs/11_1.txt")).unwrap();
+    assert_eq!(Day11::part1(&input), 10605);
+    assert_eq!(Day11::part2(&input), 2713310158);
+}
+
+#[test]
+fn day11() {
+    let input = Day11::parse(Day11::INPUT).unwrap();
+    assert_eq!(Day11::part1(&input), 61005);
+    assert_eq!(Day11::part2(&input), 20567144694);
+}
+
+#[test]
+fn day12_example() {
+    let input = Day12::parse(include_str!("inputs/12_1.txt")).unwrap();
+    assert_eq!(Day12::part1(&input), 31);
+    assert_eq!(Day12::part2(&input), 29);
+}
+
+#[test]
+fn day12() {
+    let input = Day12::parse(Day12::INPUT).unwrap();
+    assert_eq!(Day12::part1(&input), 490);
+    assert_eq!(Day12::part2(&input), 488);
+}
+
+#[test]
+fn day13_example() {
+    let input = Day13::parse(include_str!("inputs/13_1.txt")).unwrap();
+    assert_eq!(Day13::part1(&input), 13);
+    assert_eq!(Day13::part2(&input), 140);
+}
+
+#[test]
+fn day13() {
+    let input = Day13::parse(Day13::INPUT).unwrap();
+    assert_eq!(Day13::part1(&input), 5806);
+    assert_eq!(Day13::part2(&input), 23600);
+}
+
+#[test]
+fn day14_example() {
+    let input = Day14::parse(include_str!("inputs/14_1.txt")).unwrap();
+    assert_eq!(Day14::part1(&input), 24);
+    assert_eq!(Day14::part2(&input), 93);
+}
+
+#[test]
+fn day14() {
+    let input = Day14::parse(Day14::INPUT).unwrap();
+    assert_eq!(Day14::part1(&input), 892);
+    assert_eq!(Day14::part2(&input), 27155);
+}
+
+#[test]
+fn day15_example() {
+    let input = Day15::parse(include_str!("inputs/15_1.txt")).unwrap();
+    assert_eq!(day15::covered_positions(&input, 10), 26);
+    assert_eq!(day15::tuning_frequency(&input, 20), Some(56000011));
+}
+
+#[test]
+fn day15() {
+    let input = Day15::parse(Day15::INPUT).unwrap();
+    assert_eq!(Day15::part1(&input), 4951427);
+    assert_eq!(Day15::part2(&input), Some(13029714573243));
+}
+
+#[test]
+fn day16_example() {
+    let input = Day16::parse(include_str!("inputs/16_1.txt")).unwrap();
+    assert_eq!(Day16::part1(&input), 1651);
+    assert_eq!(Day16::part2(&input), 1707);
+}
+
+#[test]
+fn day16() {
+    let input = Day16::parse(Day16::INPUT).unwrap();
+    assert_eq!(Day16::part1(&input), 1754);
+    assert_eq!(Day16::part2(&input), 2474);
+}
+
+#[test]
+fn day17_example() {
+    let input = Day17::parse(include_str!("inputs/17_1.txt")).unwrap();
+    assert_eq!(Day17::part1(&input), 3068);
+    assert_eq!(Day17::part2(&input), 1514285714288);
+}
+
+#[test]
+fn day17() {
+    let input = Day17::parse(Day17::INPUT).unwrap();
+    assert_eq!(Day17::part1(&input), 3171);
+    assert_eq!(Day17::part2(&input), 1586627906921);
+}
+
+#[test]
+fn day18_example() {
+    let input = Day18::parse(include_str!("inputs/18_1.txt")).unwrap();
+    assert_eq!(Day18::part1(&input), 64);
+    assert_eq!(Day18::part2(&input), 58);
+}
+
+#[test]
+fn day18() {
+    let input = Day18::parse(Day18::INPUT).unwrap();
+    assert_eq!(Day18::part1(&input), 4300);
+    assert_eq!(Day18::part2(&input), 2490);
+}
+
+#[test]
+fn day19_example() {
+    let input = Day19::parse(include_str!("inputs/19_1.txt")).unwrap();
+    assert_eq!(Day19::part1(&input), 33);
+    assert_eq!(Day19::part2(&input), 3472);
+}
+
+#[test]
+#[ignore = "takes about a minute"]
+fn day19() {
+    let input = Day19::parse(Day19::INPUT).unwrap();
+    assert_eq!(Day19::part1(&input), 1589);
+    assert_eq!(Day19::part2(&input), 29348);
+}
+
+#[test]
+fn day20_example() {
+    let input = Day20::parse(include_str!("inputs/20_1.txt")).unwrap();
+    assert_eq!(Day20::part1(&input), 3);
+    assert_eq!(Day20::part2(&input), 1623178306);
+}
+
+#[test]
+fn day20() {
+    let input = Day20::parse(Day20::INPUT).unwrap();
+    assert_eq!(Day20::part1(&input), 8028);
+    assert_eq!(Day20::part2(&input), -12876673501498);
+}
+
+#[test]
+fn day21_example() {
+    let input = Day21::parse(include_str!("inputs/21_1.txt")).unwrap();
+    assert_eq!(Day21::part1(&input), 152);
+    assert_eq!(Day21::part2(&input), 301);
+}
+
+#[test]
+fn day21() {
+    let input = Day21::parse(Day21::INPUT).unwrap();
+    assert_eq!(Day21::part1(&input), 324122188240430);
+    assert_eq!(Day21::part2(&input), 3412650897405);
+}
+
+#[test]
+fn day22_example() {
+    let input = Day22::parse(include_str!("inputs/22_1.txt")).unwrap();
+    assert_eq!(Day22::part1(&input), 6032);
+    assert_eq!(day22::cube_password(&input, &day22::Cube::example()), 5031);
+}
+
+#[test]
+fn day22() {
+    let input = Day22::parse(Day22::INPUT).unwrap();
+    assert_eq!(Day22::part1(&input), 88226);
+    assert_eq!(Day22::part2(&input), 57305);
+}
+
+#[test]
+fn day23_example() {
+    let input = Day23::parse(include_str!("inputs/23_2.txt")).unwrap();
+    assert_eq!(Day23::part1(&input), 110);
+    assert_eq!(Day23::part2(&input), 20);
+}
+
+#[test]
+fn day23() {
+    let input = Day23::parse(Day23::INPUT).unwrap();
+    assert_eq!(Day23::part1(&input), 4109);
+    assert_eq!(Day23::part2(&input), 1055);
+}
+
+#[test]
+fn day24_example() {
+    let input = Day24::parse(include_str!("inputs/24_1.txt")).unwrap();
+    assert_eq!(Day24::part1(&input), 18);
+    assert_eq!(Day24::part2(&input), 54);
+}
+
+#[test]
+fn day24() {
+    let input = Day24::parse(Day24::INPUT).unwrap();
+    assert_eq!(Day24::part1(&input), 253);
+    assert_eq!(Day24::part2(&input), 794);
+}
+
+#[test]
+fn day25_example() {
+    let input = Day25::parse(include_str!("inputs/25_1.txt")).unwrap();
+    assert_eq!(Day25::part1(&input), "2=-1=0");
+    assert_eq!(Day25::part1(&vec![]), "0");
+    assert_eq!(Day25::part1(&vec![-3, -5]), "=2");
+}
+
+#[test]
+fn day25() {
+    let input = Day25::parse(Day25::INPUT).unwrap();
+    assert_eq!(Day25::part1(&input), "2-==10--=-0101==1201");
+}
+
+#[test]
+fn report_escaping() {
+    let records = [Record {
+        day: 10,
+        part: 2,
+        answer: Some("a \"b\", c\nd"),
+        parse_ms: 0.5,
+        duration_ms: 1.25,
+        input: "in\\put.txt",
+    }];
+    assert_eq!(
+        to_json(&records),
+        "[\n  {\"day\": 10, \"part\": 2, \"answer\": \"a \\\"b\\\", c\\nd\", \"parse_ms\": 0.500, \"duration_ms\": 1.250, \"input\": \"in\\\\put.txt\"}\n]\n"
+    );
+    assert_eq!(
+        to_csv(&records),
+        "day,part,answer,parse_ms,duration_ms,input\n10,2,\"a \"\"b\"\", c\nd\",0.500,1.250,in\\put.txt\n"
+    );
+}
+
+#[test]
+fn report_unsolved() {
+    let records = [Record {
+        day: 15,
+        part: 2,
+        answer: None,
+        parse_ms: 0.5,
+        duration_ms: 0.0,
+        input: "src/inputs/15.txt",
+    }];
+    assert_eq!(
+        to_json(&records),
+        "[\n  {\"day\": 15, \"part\": 2, \"answer\": null, \"parse_ms\": 0.500, \"duration_ms\": 0.000, \"input\": \"src/inputs/15.txt\"}\n]\n"
+    );
+    assert_eq!(
+        to_csv(&records),
+        "day,part,answer,parse_ms,duration_ms,input\n15,2,,0.500,0.000,src/inputs/15.txt\n"
+    );
+}
+
+#[test]
+fn bench_stats() {
+    let samples = [4, 1, 3, 2].map(Duration::from_millis);
+    let stats = Stats::new(&samples);
+    assert_eq!(stats.min, Duration::from_millis(1));
+    assert_eq!(stats.median, Duration::from_micros(2500));
+    assert_eq!(stats.mean, Duration::from_micros(2500));
+    assert_eq!(stats.stddev.as_micros(), 1118);
+}
+
+#[test]
+fn bench_baseline_roundtrip() {
+    let stats = Stats::new(&[3, 5, 10].map(Duration::from_nanos));
+    let entries = vec![
+        Entry {
+            day: 19,
+            stage: Stage::Parse,
+            stats,
+        },
+        Entry {
+            day: 19,
+            stage: Stage::Part(2),
+            stats,
+        },
+    ];
+    assert_eq!(from_baseline(&to_baseline(&entries)), Ok(entries));
+    assert!(from_baseline("19 3 1 2 3 4").is_err());
+}
+
+/// Parses `input` as the input of day `S`, expecting an error at the given position.
+fn parse_error<S: Solution>(input: &str) -> (usize, usize, String) {
+    let err = S::parse(input).err().unwrap();
+    (err.line, err.column, err.found)
+}
+
+#[test]
+fn parse_errors() {
+    let monkey = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old % 19
+  Test: divisible by 23
+    If true: throw to monkey 0
+    If false: throw to monkey 0";
+    assert_eq!(parse_error::<Day11>(monkey), (3, 24, String::from("%")));
+    let monkey = monkey.replace('%', "*");
+    assert_eq!(parse_error::<Day11>(&monkey), (6, 32, String::new()));
+    assert_eq!(parse_error::<Day11>(""), (1, 1, String::new()));
+
+    let stacks = "[A]    \n[B] [C]\n 1   2 \n\nmove 1 from 2 to 1\nmove 2 from 2 to 1";
+    assert_eq!(parse_error::<Day5>(stacks), (6, 6, String::from("2")));
+    let stacks = "[A]    \n[B] [C]\n 1   2 \n\nmove 1 from 3 to 1";
+    assert_eq!(parse_error::<Day5>(stacks), (5, 13, String::from("3")));
+
+    let program = "noop\naddx 3\naddx -5\n";
+    assert_eq!(parse_error::<Day10>(program), (3, 8, String::new()));
+
+    let valve = "Valve AA has flow rate=x3; tunnels lead to valves AA";
+    assert_eq!(parse_error::<Day16>(valve), (1, 24, String::from("x3")));
+
+    let blueprint = "Blueprint 1: Each ore robot costs 4 ore. \
+        Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. \
+        Each geode robot costs two ore and 7 obsidian.";
+    assert_eq!(
+        parse_error::<Day19>(blueprint),
+        (1, 139, String::from("two"))
+    );
+
+    let monkeys = "root: pppw + sjmn\npppw: 3 ^ 4\nsjmn: 5";
+    assert_eq!(parse_error::<Day21>(monkeys), (2, 9, String::from("^")));
+}
+
+#[test]
+fn parse_combinators() {
+    let input = "  [A]\n[B] [C]\n\n\nmove 1 from 2 to 3\n  \nx=-4, y=2-7\n";
+    let paragraphs: Vec<&str> = parse::paragraphs(input).collect();
+    assert_eq!(
+        paragraphs,
+        ["  [A]\n[B] [C]", "move 1 from 2 to 3", "x=-4, y=2-7"]
+    );
+    assert_eq!(
+        parse::lines(paragraphs[0]).collect::<Vec<_>>(),
+        ["[A]", "[B] [C]"]
+    );
+
+    let fields = parse::pattern(input, paragraphs[1], "move {} from {} to {}");
+    assert_eq!(fields, Ok(["1", "2", "3"]));
+    let err = parse::pattern::<3>(input, paragraphs[1], "move {} to {} from {}");
+    assert_eq!(err.map_err(|err| (err.line, err.column)), Err((5, 18)));
+
+    assert_eq!(parse::integers(input, paragraphs[2]), Ok(vec![-4, 2, 7]));
+    assert_eq!(parse::integers_n(input, paragraphs[2]), Ok([-4, 2, 7]));
+    assert!(parse::integers_n::<i32, 2>(input, paragraphs[2]).is_err());
+
+    let digit = |ch: char| ch.to_digit(10);
+    let (grid, _) = parse::grid("12\n34\n", "12\n34\n", "a digit", "", digit).unwrap();
+    assert_eq!((grid.width(), grid.height()), (2, 2));
+    assert_eq!(*grid.at((1, 1)), 4);
+    assert!(parse::grid("12\n3\n", "12\n3\n", "a digit", "", digit).is_err());
+}
+
+#[test]
+fn array2d_from_str_map() {
+    let map = "  S.\n.#\n#..E\n";
+    let (grid, markers) = Array2D::from_str_map(map, map, "SE", |ch| match ch {
+        ' ' => Some(0),
+        '.' | 'S' | 'E' => Some(1),
+        '#' => Some(2),
+        _ => None,
+    })
+    .unwrap();
+    assert_eq!((grid.width(), grid.height()), (4, 3));
+    assert_eq!(markers.get('S'), Some(vec2(2, 0)));
+    assert_eq!(markers.get('E'), Some(vec2(3, 2)));
+    assert_eq!(markers.all('#').count(), 0);
+
+    let render = grid.render(|cell| [' ', '.', '#'][*cell]);
+    assert_eq!(render, "  ..\n.#  \n#...\n");
+
+    let err = Array2D::from_str_map(map, map, "", |ch| (ch != '#').then_some(0)).unwrap_err();
+    assert_eq!((err.line, err.column), (2, 2));
+
+    let gap = "S.\n.#\n  \n#E\n\n";
+    let err = Array2D::from_str_map(gap, gap, "", |_| Some(0)).unwrap_err();
+    assert_eq!((err.line, err.column), (3, 1));
+    assert!(parse::grid(gap, gap, "a cell", "", |_| Some(0)).is_err());
+    let (grid, _) = Array2D::from_str_map(gap, &gap[..5], "", |_| Some(0)).unwrap();
+    assert_eq!(grid.height(), 2);
+}
+
+#[test]
+fn array2d_bounds_and_neighbors() {
+    let mut grid = Array2D::new(3, 2);
+    for (i, cell) in grid.iter_mut().enumerate() {
+        *cell = i;
+    }
+    assert!(grid.in_bounds((2, 1)));
+    assert!(!grid.in_bounds((3, 1)));
+    assert!(!grid.in_bounds(vec2(-1, 0)));
+    assert_eq!(grid.get((1, 1)), Some(&4));
+    assert_eq!(grid.get(vec2(0, -1)), None);
+    assert_eq!(grid.get((0usize, 2usize)), None);
+
+    let corner: Vec<_> = grid.neighbors4((0, 0)).collect();
+    assert_eq!(corner, [vec2(1, 0), vec2(0, 1)]);
+    assert_eq!(grid.neighbors8((1, 0)).count(), 5);
+    assert_eq!(grid.neighbors4_wrapping((0, 0)).count(), 4);
+
+    assert_eq!(grid.wrap(vec2(-1, 2)), vec2(2, 0));
+    assert_eq!(*grid.at_wrapping(vec2(4, -1)), 4);
+    assert_eq!(grid.neighbors8_wrapping((0, 0)).count(), 8);
+
+    *grid.at_wrapping_mut(vec2(-1, -1)) = 10;
+    *grid.get_mut((0, 0)).unwrap() += 10;
+    assert!(grid.get_mut(vec2(3, 0)).is_none());
+    for cell in grid.column_mut(1) {
+        *cell = 20;
+    }
+    grid.slice_mut(0)[0] += 1;
+    assert_eq!(grid.slice(0), [11, 20, 2]);
+    assert_eq!(grid.slice(1), [3, 20, 10]);
+}
+
+#[test]
+#[should_panic(expected = "negative array index")]
+fn array2d_negative_index() {
+    let grid: Array2D<u8> = Array2D::new(3, 3);
+    grid.at(vec2(-1, 0));
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn array2d_index_past_row() {
+    let grid: Array2D<u8> = Array2D::new(3, 3);
+    grid.at((3, 0));
+}
+
+#[test]
+#[should_panic(expected = "doesn't fit i32")]
+fn array2d_neighbors_of_huge_position() {
+    let grid: Array2D<u8> = Array2D::new(3, 3);
+    grid.neighbors4([3_000_000_000usize, 0]).count();
+}
+
+#[test]
+fn array2d_without_columns() {
+    let mut grid: Array2D<u8> = Array2D::new(0, 3);
+    assert_eq!(grid.rows().count(), 0);
+    assert_eq!(grid.rows_mut().count(), 0);
+    assert_eq!(grid.flip_x().rows().count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "empty 0x3 array")]
+fn array2d_wrap_empty() {
+    let grid: Array2D<u8> = Array2D::new(0, 3);
+    grid.wrap((1, 1));
+}
+
+#[test]
+fn array2d_transforms() {
+    let text = "abc\ndef\n";
+    let (grid, _) = Array2D::from_str_map(text, text, "", Some).unwrap();
+    let render = |grid: &Array2D<char>| grid.render(|ch| *ch);
+
+    assert_eq!(render(&grid.transpose()), "ad\nbe\ncf\n");
+    assert_eq!(render(&grid.rotate90()), "da\neb\nfc\n");
+    assert_eq!(render(&grid.rotate180()), "fed\ncba\n");
+    assert_eq!(render(&grid.rotate270()), "cf\nbe\nad\n");
+    assert_eq!(render(&grid.flip_x()), "cba\nfed\n");
+    assert_eq!(render(&grid.flip_y()), "def\nabc\n");
+    assert_eq!(render(&grid.rotate90().rotate270()), text);
+
+    let column: String = grid.column(1).collect();
+    assert_eq!(column, "be");
+    let columns: Vec<String> = grid
+        .columns()
+        .map(|column| column.rev().collect())
+        .collect();
+    assert_eq!(columns, ["da", "eb", "fc"]);
+    assert_eq!(grid.rows().nth(1), Some(&['d', 'e', 'f'][..]));
+    assert_eq!(render(&grid.crop_rows(1..2)), "def\n");
+}
+
+#[test]
+fn infinite_grid() {
+    let mut grid = InfiniteGrid::with_default('.');
+    assert_eq!(grid.bounds(), None);
+    assert_eq!(*grid.at(vec2(100, -100)), '.');
+
+    grid.set(vec2(2, 1), 'a');
+    grid.set(vec2(-3, -2), 'b');
+    grid.set(vec2(40, 0), 'c');
+    assert_eq!(grid.bounds(), Some(Rect::new(vec2(-3, -2), vec2(40, 1))));
+    assert_eq!((grid.width(), grid.height()), (44, 4));
+    assert_eq!(*grid.at(vec2(2, 1)), 'a');
+    assert_eq!(*grid.at(vec2(-3, -2)), 'b');
+    assert_eq!(*grid.at(vec2(0, 0)), '.');
+
+    let written: String = grid
+        .iter()
+        .map(|(_, ch)| *ch)
+        .filter(|ch| *ch != '.')
+        .collect();
+    assert_eq!(written, "bca");
+    assert_eq!(*grid.to_array2d().at((5, 3)), 'a');
+}
+
+#[test]
+fn bitgrid() {
+    let mut grid = BitGrid::new(7, 3);
+    grid.set((1, 0), true);
+    grid.set(vec2(6, 2), true);
+    assert!(grid.at((1, 0)) && !grid.at((0, 0)));
+    assert_eq!(grid.get(vec2(7, 0)), None);
+    assert_eq!(grid.row(2), 0b100_0000);
+
+    grid.or_row(1, 0b1111_0000);
+    assert_eq!(grid.row(1), 0b111_0000);
+    grid.shl_row(0, 2);
+    assert_eq!(grid.row(0), 0b1000);
+    grid.and_row(1, 0b010_0000);
+    assert_eq!(grid.count_ones(), 3);
+    assert_eq!(grid.render('#', '.'), "...#...\n.....#.\n......#\n");
+    assert_eq!(grid.iter().position(|bit| bit), Some(3));
+    assert_eq!(grid.iter().filter(|&bit| bit).count(), 3);
+
+    grid.fill(true);
+    assert_eq!(grid.count_ones(), 21);
+
+    let mut wide = BitGrid::new(130, 2);
+    wide.set((63, 1), true);
+    wide.set((129, 1), true);
+    wide.shl_row(1, 1);
+    assert!(wide.at((64, 1)) && !wide.at((63, 1)));
+    assert_eq!(wide.row_count_ones(1), 1);
+    wide.shr_row(1, 64);
+    assert!(wide.at((0, 1)));
+    assert_eq!(wide.row_words(1), [1, 0, 0]);
+
+    let mut empty = BitGrid::new(0, 2);
+    empty.set_row(1, 0b1);
+    assert_eq!(empty.row(1), 0);
+    assert_eq!(empty.render('#', '.'), "\n\n");
+
+    let mut narrow = BitGrid::new(64, 2);
+    narrow.set_row(1, u64::MAX);
+    assert_eq!((narrow.row(0), narrow.row(1)), (0, u64::MAX));
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn bitgrid_out_of_bounds() {
+    let mut grid = BitGrid::new(3, 2);
+    grid.set((3, 0), true);
+}
+
+#[test]
+#[should_panic(expected = "don't fit a word")]
+fn bitgrid_wide_row() {
+    BitGrid::new(65, 1).row(0);
+}
+
+#[test]
+fn image_export() {
+    let mut grid = Array2D::new(2, 1);
+    grid.set((1, 0), true);
+
+    let ppm = grid.to_ppm(|on| if *on { [255, 0, 0] } else { [0, 0, 0] });
+    assert_eq!(ppm, b"P6\n2 1\n255\n\0\0\0\xff\0\0");
+    let pgm = grid.to_pgm(|on| *on as u8 * 255);
+    assert_eq!(pgm, b"P5\n2 1\n255\n\0\xff");
+
+    let dir = std::env::temp_dir().join(format!("aoc-frames-{}", std::process::id()));
+    let mut frames = Frames::new(&dir, "test").unwrap();
+    frames.write_pgm(&grid, |on| *on as u8);
+    frames.write_ppm(&grid, |_| [1, 2, 3]);
+    assert_eq!(frames.finish().unwrap(), 2);
+    assert_eq!(
+        std::fs::read(dir.join("test_00000.pgm")).unwrap(),
+        b"P5\n2 1\n255\n\0\x01"
+    );
+    assert!(dir.join("test_00001.ppm").exists());
+
+    // After a failed write, nothing more is written and `finish` reports the error.
+    let mut frames = Frames::new(&dir, "gone").unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    frames.write_pgm(&grid, |on| *on as u8);
+    std::fs::create_dir_all(&dir).unwrap();
+    frames.write_pgm(&grid, |on| *on as u8);
+    assert!(frames.finish().is_err());
+    assert!(!dir.join("gone_00000.pgm").exists());
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn array3d_neighbors_and_flood_fill() {
+    let mut grid: Array3D<u8> = Array3D::new(3, 3, 3);
+    assert_eq!(grid.get((2, 2, 2)), Some(&0));
+    assert_eq!(grid.get((0, -1, 0)), None);
+    assert_eq!(grid.neighbors6((0, 0, 0)).count(), 3);
+    assert_eq!(grid.neighbors6((1, 1, 1)).count(), 6);
+    assert_eq!(grid.neighbors26((1, 1, 1)).count(), 26);
+    assert_eq!(grid.neighbors26((0, 0, 0)).count(), 7);
+
+    // a wall at x = 1 keeps the fill on one side
+    for y in 0..3 {
+        for z in 0..3 {
+            grid.set((1, y, z), 1);
+        }
+    }
+    assert_eq!(grid.flood_fill((0, 0, 0), |v| *v == 0, 2), 9);
+    assert_eq!(grid.plane(1), [2, 1, 0, 2, 1, 0, 2, 1, 0]);
+    assert_eq!(grid.slice(2, 0), [2, 1, 0]);
+    assert_eq!(*grid.plane_array(2).at((0, 1)), 2);
+    grid.slice_mut(0, 1)[2] = 3;
+    grid.plane_mut(2).fill(4);
+    assert_eq!(grid.plane(1), [2, 1, 3, 2, 1, 0, 2, 1, 0]);
+    assert_eq!(grid.plane(2), [4; 9]);
+    assert_eq!(
+        format!("{:?}", Array3D::<u8>::new(2, 1, 2)),
+        "[[[0, 0]], [[0, 0]]]"
+    );
+}
+
+#[test]
+fn arrayn_four_dimensions() {
+    let mut grid: ArrayN<u8, 4> = ArrayN::from_shape([3, 3, 3, 3]);
+    assert_eq!(grid.strides(), [1, 3, 9, 27]);
+    assert_eq!(grid.neighbors([1usize, 1, 1, 1]).count(), 80);
+    assert_eq!(grid.neighbors_orthogonal([0i32, 0, 0, 0]).count(), 4);
+    assert_eq!(
+        grid.neighbors_orthogonal([1usize, 1, 1, 1]).next(),
+        Some([0, 1, 1, 1])
+    );
+
+    grid.set([2usize, 1, 0, 2], 7);
+    assert_eq!(grid.iter().position(|v| *v == 7), Some(2 + 3 + 54));
+    assert_eq!(grid.get([2i32, 1, 0, 3]), None);
+    assert_eq!(grid.flood_fill([0usize; 4], |v| *v == 0, 1), 80);
+
+    let grid: Array2D<u8> = Array2D::new(2, 3);
+    assert_eq!(grid.shape(), [2, 3]);
+    assert_eq!(grid.map(|v| *v as u32 + 1).iter().sum::<u32>(), 6);
+}
+
+#[test]
+fn mesh_matches_day18() {
+    for input in [include_str!("inputs/18_1.txt"), Day18::INPUT] {
+        let cubes = Day18::parse(input).unwrap();
+        let size = cubes.iter().map(|c| c.x.max(c.y).max(c.z)).max().unwrap() as usize + 3;
+        let mut grid: Array3D<u8> = Array3D::new(size, size, size);
+        for cube in &cubes {
+            grid.set(*cube + (1, 1, 1), 1);
+        }
+
+        let mesh = Mesh::new(&grid, |cell| *cell == 1);
+        let greedy = Mesh::greedy(&grid, |cell| *cell == 1);
+        assert_eq!(mesh.quads.len() as i64, mesh.area());
+        assert_eq!(mesh.area(), Day18::part1(&cubes) as i64);
+        assert_eq!(greedy.area(), mesh.area());
+        assert!(greedy.quads.len() < mesh.quads.len());
+
+        grid.flood_fill((0, 0, 0), |cell| *cell == 0, 2);
+        let exterior = Mesh::greedy(&grid, |cell| *cell != 2);
+        assert_eq!(exterior.area(), Day18::part2(&cubes) as i64);
+    }
+}
+
+#[test]
+fn mesh_export() {
+    let mut grid: Array3D<bool> = Array3D::new(2, 1, 1);
+    grid.fill(true);
+    let mesh = Mesh::greedy(&grid, |cell| *cell);
+    assert_eq!(mesh.quads.len(), 6);
+    assert_eq!(mesh.area(), 10);
+
+    let obj = mesh.to_obj();
+    assert_eq!(obj.lines().filter(|l| l.starts_with("v ")).count(), 8);
+    assert_eq!(obj.lines().filter(|l| l.starts_with("f ")).count(), 6);
+    let stl = mesh.to_stl("bar");
+    assert!(stl.starts_with("solid bar\n") && stl.ends_with("endsolid bar\n"));
+    assert_eq!(stl.matches("facet normal").count(), 12);
+    assert!(stl.contains("facet normal 1 0 0\n    outer loop\n      vertex 2 0 0\n"));
+}
+
+#[test]
+fn vector_api() {
+    let a = vec3(1, -2, 3);
+    let b = vec3(4, 5, -6);
+    assert_eq!(a + b, vec3(5, 3, -3));
+    assert_eq!(b - (1, 1, 1), vec3(3, 4, -7));
+    assert_eq!(-a, vec3(-1, 2, -3));
+    assert_eq!(b / 2, vec3(2, 2, -3));
+    assert_eq!(b % vec3(3, 3, 4), vec3(1, 2, -2));
+    assert_eq!(a.min(b), vec3(1, -2, -6));
+    assert_eq!(a.max(b).max_element(), 5);
+    assert!(a.lt(b).any() && !a.lt(b).all() && !a.lt(b).none());
+    assert_eq!((a.sum(), a.product()), (2, -6));
+    assert_eq!(a.map(|c| c * 10), vec3(10, -20, 30));
+    assert_eq!(a.zip_with(b, |p, q| p * q).sum(), -24);
+    assert_eq!((a[0], a[1], a[2]), (1, -2, 3));
+
+    let mut v = vec2(7, 9);
+    v %= 4;
+    v /= (1, 3);
+    v[1] += 1;
+    assert_eq!(v, Vec2i32::from([3, 1]));
+    assert_eq!(<[i32; 2]>::from(v), [3, 1]);
+
+    let w: Vec4<u32> = vec4(1, 2, 3, 4) * 2;
+    assert_eq!(w % (3, 3, 3, 3), vec4(2, 1, 0, 2));
+    assert_eq!(format!("{:?}", w), "Vec4(2, 4, 6, 8)");
+}
+
+#[test]
+fn vector_geometry() {
+    let a = vec2(1, -2);
+    let b = vec2(4, 5);
+    assert_eq!(a.manhattan(b), 10);
+    assert_eq!(a.chebyshev(b), 7);
+    assert_eq!(a.distance_squared(b), 58);
+    assert_eq!(a.dot(b), -6);
+    assert_eq!(vec2(1, 0).cross(vec2(0, 1)), 1);
+    assert_eq!(vec3(1, 0, 0).cross(vec3(0, 1, 0)), vec3(0, 0, 1));
+
+    let east = vec2(1, 0);
+    assert_eq!(east.rot_cw(), vec2(0, 1));
+    assert_eq!(east.rot_ccw(), vec2(0, -1));
+    assert_eq!(east.rot_cw().rot_cw().rot_cw().rot_cw(), east);
+
+    let line: Vec<_> = vec2(0, 0).line_to(vec2(4, -2)).collect();
+    assert_eq!(
+        line,
+        [
+            vec2(0, 0),
+            vec2(1, -1),
+            vec2(2, -1),
+            vec2(3, -2),
+            vec2(4, -2)
+        ]
+    );
+    assert_eq!(vec2(3, 7).line_to(vec2(3, 2)).len(), 6);
+    assert_eq!(a.line_to(a).collect::<Vec<_>>(), [a]);
+
+    assert!(Vec2i32::NEIGHBORS8
+        .iter()
+        .all(|ofs| ofs.chebyshev(Vec2i32::default()) == 1));
+    assert!(Vec3i32::NEIGHBORS6.iter().all(|ofs| ofs.abs().sum() == 1));
+}
+
+#[test]
+fn heading() {
+    assert_eq!(Heading::East.turn_right(), Heading::South);
+    assert_eq!(Heading::North.turn_left(), Heading::West);
+    assert_eq!(Heading::West.turn_around(), Heading::East);
+    assert_eq!(Heading::South.rotate(-3), Heading::West);
+
+    for heading in Heading::ALL {
+        assert_eq!(heading.turn_right().to_vec2(), heading.to_vec2().rot_cw());
+        assert_eq!(vec2(3, 3) + heading - heading.to_vec2(), vec2(3, 3));
+        assert_eq!(Compass::from(heading).to_vec2(), heading.to_vec2());
+        assert_eq!(Heading::try_from(Compass::from(heading)), Ok(heading));
+    }
+    let sum = Heading::ALL
+        .iter()
+        .fold(Vec2i32::default(), |sum, heading| sum + *heading);
+    assert_eq!(sum, Vec2i32::default());
+
+    for (chars, heading) in ["N^U", "E>R", "SvD", "W<L"].iter().zip(Heading::ALL) {
+        assert!(chars
+            .chars()
+            .all(|ch| Heading::from_char(ch) == Some(heading)));
+    }
+    assert!(Heading::from_char('x').is_none());
+    assert!(Heading::parse("NE", "NE").is_err());
+
+    assert_eq!(Compass::North.rotate(-1), Compass::NorthWest);
+    assert!(Compass::SouthEast.is_diagonal() && !Compass::South.is_diagonal());
+    assert_eq!(
+        Heading::try_from(Compass::SouthEast),
+        Err(Compass::SouthEast)
+    );
+    assert_eq!(Compass::NorthEast.turn_right(), Compass::SouthEast);
+    assert_eq!(Compass::North.turn_left(), Compass::West);
+    assert_eq!(Compass::SouthWest.turn_around(), Compass::NorthEast);
+    for compass in Compass::ALL {
+        assert_eq!(compass.turn_right().to_vec2(), compass.to_vec2().rot_cw());
+    }
+
+    let parsed: Vec<_> = ["N", "NE", ">", "SE", "D", "SW", "W", "NW"]
+        .iter()
+        .map(|s| Compass::parse(s, s).unwrap())
+        .collect();
+    assert_eq!(parsed, Compass::ALL);
+    assert_eq!(Compass::from_char('<'), Some(Compass::West));
+    assert!(Compass::parse("EN", "EN").is_err() && Compass::parse("", "").is_err());
+    let sum = Compass::ALL
+        .iter()
+        .fold(Vec2i32::default(), |sum, compass| sum + *compass);
+    assert_eq!(sum, Vec2i32::default());
+}
+
+#[test]
+fn vector_overflow_arithmetic() {
+    let size = vec2(5, 3);
+    assert_eq!(vec2(-1, 7).rem_euclid(size), vec2(4, 1));
+    assert_eq!(vec2(-1, 7).div_euclid(size), vec2(-1, 2));
+
+    let a: Vec4<u32> = vec4(3, 2, 1, 0);
+    assert_eq!(a.checked_sub(vec4(1, 1, 1, 0)), Some(vec4(2, 1, 0, 0)));
+    assert_eq!(a.checked_sub(vec4(0, 0, 0, 1)), None);
+    assert_eq!(a.checked_add(vec4(u32::MAX, 0, 0, 0)), None);
+    assert_eq!(a.saturating_sub(vec4(5, 1, 5, 1)), vec4(0, 1, 0, 0));
+    assert_eq!(
+        vec3(250u8, 1, 2).saturating_add(vec3(10, 10, 10)),
+        vec3(255, 11, 12)
+    );
+    assert_eq!(
+        vec3(250u8, 1, 2).wrapping_add(vec3(10, 10, 10)),
+        vec3(4, 11, 12)
+    );
+    assert_eq!(vec2(i32::MAX, 2).wrapping_mul(vec2(2, 2)), vec2(-2, 4));
+    assert_eq!(
+        vec2(i32::MAX, 2).checked_mul(vec2(1, 2)),
+        Some(vec2(i32::MAX, 4))
+    );
+}
+
+#[test]
+fn vector_casts_swizzles_and_floats() {
+    let v = vec3(1, -2, 3);
+    assert_eq!(v.cast::<i64>(), vec3(1i64, -2, 3));
+    assert!(v.try_cast::<usize>().is_err());
+    assert_eq!(v.abs().try_cast::<u8>(), Ok(vec3(1u8, 2, 3)));
+
+    assert_eq!(v.zyx(), vec3(3, -2, 1));
+    assert_eq!(v.zx(), vec2(3, 1));
+    assert_eq!(vec2(1, 2).yx(), vec2(2, 1));
+    let w = vec4(1, 2, 3, 4);
+    assert_eq!(
+        (w.wzyx(), w.ywx(), w.xz()),
+        (vec4(4, 3, 2, 1), vec3(2, 4, 1), vec2(1, 3))
+    );
+    assert_eq!(
+        (w.xx(), w.xxy(), w.zzz()),
+        (vec2(1, 1), vec3(1, 1, 2), vec3(3, 3, 3))
+    );
+    assert_eq!((w.xyzw(), v.xyzx()), (w, vec4(1, -2, 3, 1)));
+    assert_eq!(vec2(1, 2).yyxy(), vec4(2, 2, 1, 2));
+
+    let f = vec2(3.0, -4.0);
+    assert_eq!(f.length(), 5.0);
+    assert_eq!(f.normalize(), vec2(0.6, -0.8));
+    assert_eq!(f.lerp(vec2(5.0, 0.0), 0.25), vec2(3.5, -3.0));
+    assert_eq!(vec2(1.5, -1.5).floor(), vec2(1.0, -2.0));
+    assert_eq!(vec2(1.5, -1.4).round(), vec2(2.0, -1.0));
+    assert_eq!(f.min(vec2(0.0, 0.0)), vec2(0.0, -4.0));
+    assert_eq!(f.max_element(), 3.0);
+    assert!(f.lt(vec2(4.0, 0.0)).all());
+    assert_eq!(f.lt(vec2(3.0, 0.0)), vec2(false, true));
+    assert_eq!(format!("{:?}", vec2(false, true)), "Vec2(false, true)");
+
+    // The aliases name the same types as the generic vectors.
+    let _: (Vec2i64, Vec2u64, Vec2usize) = (vec2(1, 2), vec2(1, 2), vec2(1, 2));
+    let _: (Vec3u32, Vec3u64, Vec3usize) = (vec3(1, 2, 3), vec3(1, 2, 3), vec3(1, 2, 3));
+    let _: (Vec4i32, Vec4i64, Vec4u64, Vec4usize) =
+        (w, w.cast(), w.try_cast().unwrap(), w.try_cast().unwrap());
+}
+
+#[test]
+#[cfg(target_endian = "little")]
+fn array_snapshots() {
+    let mut grid: Array3D<Vec2i32> = Array3D::new(3, 2, 2);
+    grid.set((2, 1, 1), vec2(-7, 9));
+    let bytes = grid.to_snapshot();
+    assert_eq!(bytes.len(), 4 + 4 + 4 + 3 * 8 + 8 + 12 * 8);
+    let loaded = Array3D::<Vec2i32>::from_snapshot(&bytes).unwrap();
+    assert_eq!(loaded.shape(), [3, 2, 2]);
+    assert_eq!(loaded.at((2, 1, 1)), &vec2(-7, 9));
+
+    let mut corrupt = bytes.clone();
+    *corrupt.last_mut().unwrap() ^= 1;
+    assert!(Array3D::<Vec2i32>::from_snapshot(&corrupt).is_err());
+    assert!(Array3D::<u32>::from_snapshot(&bytes).is_err());
+    assert!(Array2D::<Vec2i32>::from_snapshot(&bytes).is_err());
+    assert!(Array3D::<Vec2i32>::from_snapshot(&bytes[..20]).is_err());
+
+    let path = std::env::temp_dir().join(format!("aoc-array-{}.bin", std::process::id()));
+    grid.save_snapshot(&path).unwrap();
+    let loaded = Array3D::<Vec2i32>::load_snapshot(&path).unwrap();
+    assert!(loaded.iter().eq(grid.iter()));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn bitgrid_snapshots() {
+    let mut bits = BitGrid::new(70, 3);
+    bits.set((69, 2), true);
+    bits.set((3, 0), true);
+    let path = std::env::temp_dir().join(format!("aoc-snapshot-{}.bin", std::process::id()));
+    bits.save_snapshot(&path).unwrap();
+    assert!(BitGrid::load_snapshot(&path).unwrap() == bits);
+    #[cfg(target_endian = "little")]
+    assert!(Array2D::<u64>::load_snapshot(&path).is_err());
+    std::fs::remove_file(path).unwrap();
+
+    // The words are little endian, and padding bits past the width have to be clear.
+    let bytes = bits.to_snapshot();
+    assert_eq!(
+        bytes[bytes.len() - 48..bytes.len() - 40],
+        [8, 0, 0, 0, 0, 0, 0, 0]
+    );
+    bits.words_mut()[1] |= 1 << 63;
+    assert!(BitGrid::from_snapshot(&bits.to_snapshot()).is_err());
+}
+
+#[test]
+fn flatmap() {
+    let mut map = FlatMap::new();
+    map.set(&"a", 1);
+    map.set(&"b", 2);
+    map.set(&"a", 3);
+    *map.at(&"c", &0) += 5;
+    *map.get_mut(&"b").unwrap() *= 10;
+    assert_eq!((map.get(&"a"), map.get(&"d")), (Some(&3), None));
+    assert_eq!(map.data(), &[("a", 3), ("b", 20), ("c", 5)]);
+    assert_eq!(format!("{map:?}"), r#"[("a", 3), ("b", 20), ("c", 5)]"#);
+
+    for (_, value) in map.iter_mut() {
+        *value += 1;
+    }
+    assert_eq!(map.iter().map(|(_, value)| value).sum::<i32>(), 31);
+    assert_eq!(map.take_data().len(), 3);
+}
+
+#[test]
+fn bounding_boxes() {
+    let rect = Rect::from_points([vec2(2, 5), vec2(-1, 3), vec2(0, 4)]).unwrap();
+    assert_eq!(rect, Rect::new(vec2(-1, 3), vec2(2, 5)));
+    assert_eq!((rect.size(), rect.area()), (vec2(4, 3), 12));
+    assert!(rect.contains(vec2(-1, 5)) && !rect.contains(vec2(3, 4)));
+    assert_eq!(Rect::<i32>::from_points([]), None);
+
+    let other = Rect::new(vec2(1, 0), vec2(6, 3));
+    assert_eq!(
+        rect.intersect(&other),
+        Some(Rect::new(vec2(1, 3), vec2(2, 3)))
+    );
+    assert_eq!(rect.union(&other), Rect::new(vec2(-1, 0), vec2(6, 5)));
+    assert_eq!(other.intersect(&Rect::new(vec2(7, 0), vec2(8, 1))), None);
+    assert_eq!(rect.expand(1).area(), 30);
+
+    let points: Vec<_> = Rect::new(vec2(0, 0), vec2(1, 1)).points().collect();
+    assert_eq!(points, [vec2(0, 0), vec2(1, 0), vec2(0, 1), vec2(1, 1)]);
+    assert_eq!(rect.points().count(), 12);
+    assert!(rect.points().all(|p| rect.contains(p)));
+
+    let grid: Array2D<u8> = Array2D::new_covering(&rect);
+    assert_eq!((grid.width(), grid.height()), (4, 3));
+
+    let cuboid = Cuboid::from_points([vec3(1, 2, 3), vec3(3, 2, 1)]).unwrap();
+    assert_eq!((cuboid.size(), cuboid.volume()), (vec3(3, 1, 3), 9));
+    assert!(cuboid.contains(vec3(2, 2, 2)) && !cuboid.contains(vec3(2, 3, 2)));
+    let above = Cuboid::new(vec3(0, 3, 0), vec3(1, 4, 1));
+    assert_eq!(cuboid.intersect(&above), None);
+    assert_eq!(cuboid.union(&above).volume(), 4 * 3 * 4);
+    assert_eq!(cuboid.points().nth(1), Some(vec3(2, 2, 1)));
+    let grid: Array3D<u8> = Array3D::new_covering(&cuboid.expand(2));
+    assert_eq!(grid.shape(), [7, 5, 7]);
+}
+
+#[test]
+fn interval_set() {
+    let mut set: IntervalSet<i64> = [0..3, 10..12, 5..7].into_iter().collect();
+    assert_eq!(set.ranges(), [0..3, 5..7, 10..12]);
+    set.insert(3..5);
+    set.insert(20..20);
+    assert_eq!(set.ranges(), [0..7, 10..12]);
+    assert_eq!(set.total_len(), 9);
+    assert!(set.contains(6) && !set.contains(7) && !set.contains(-1));
+    assert!(set.contains_range(&(2..7)) && !set.contains_range(&(6..11)));
+
+    set.remove(2..4);
+    set.remove(11..30);
+    assert_eq!(set.ranges(), [0..2, 4..7, 10..11]);
+    assert_eq!(set.gaps().collect::<Vec<_>>(), [2..4, 7..10]);
+    assert_eq!(
+        set.complement(-1..12).ranges(),
+        [-1..0, 2..4, 7..10, 11..12]
+    );
+
+    let other = IntervalSet::from(1..5).intersection(&IntervalSet::from(-5..20));
+    assert_eq!(set.intersection(&other).ranges(), [1..2, 4..5]);
+    set.subtract(&other);
+    assert_eq!(set.ranges(), [0..1, 5..7, 10..11]);
+    set.insert(i64::MIN..i64::MAX);
+    assert_eq!(set, IntervalSet::from(i64::MIN..i64::MAX));
+    assert_eq!(set.total_len(), u64::MAX as u128);
+    let wide: IntervalSet<i8> = [-128..0, 0..127].into_iter().collect();
+    assert_eq!(wide.total_len(), 255);
+    assert_eq!(IntervalSet::from(0..u128::MAX).total_len(), u128::MAX);
+    let bytes: IntervalSet<u8> = IntervalSet::from(0..255);
+    assert!(IntervalSet::new().intersection(&bytes).is_empty());
+}
@@ -1,58 +1,139 @@
-use std::{fmt::Display, time::Instant};
+use std::time::{Duration, Instant};
 
-pub struct Timer<T1: Display, T2: Display> {
-    day: i32,
-    start: Instant,
-    result1: Option<T1>,
-    result2: Option<T2>,
+use crate::solution::Answer;
+
+pub struct Measurement {
+    /// `None` if the part found no answer.
+    pub answer: Option<String>,
+    pub duration: Duration,
+}
+
+/// Runs the parts of a day and records their answers, along with how long parsing and each part took.
+pub struct Timer {
+    day: u32,
+    parse: Duration,
+    part1: Option<Measurement>,
+    part2: Option<Measurement>,
+}
+
+fn measure<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+pub fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
 }
 
-impl<T1: Display, T2: Display> Timer<T1, T2> {
-    pub fn new(day: i32) -> Self {
-        let start = Instant::now();
+impl Timer {
+    pub fn new(day: u32) -> Self {
         Self {
             day,
-            start,
-            result1: None,
-            result2: None,
+            parse: Duration::ZERO,
+            part1: None,
+            part2: None,
         }
     }
 
-    pub fn set1(&mut self, result: T1) {
-        self.result1 = Some(result);
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    pub fn parse<T>(&mut self, f: impl FnOnce() -> T) -> T {
+        let (input, duration) = measure(f);
+        self.parse = duration;
+        input
     }
 
-    pub fn set1_once(&mut self, result: T1) {
-        if self.result1.is_none() {
-            self.result1 = Some(result);
+    pub fn part1<T: Answer>(&mut self, f: impl FnOnce() -> T) {
+        let (answer, duration) = measure(f);
+        let answer = answer.answer();
+        self.part1 = Some(Measurement { answer, duration });
+    }
+
+    pub fn part2<T: Answer>(&mut self, f: impl FnOnce() -> T) {
+        let (answer, duration) = measure(f);
+        let answer = answer.answer();
+        self.part2 = Some(Measurement { answer, duration });
+    }
+
+    pub fn parse_duration(&self) -> Duration {
+        self.parse
+    }
+
+    pub fn result(&self, part: u32) -> Option<&Measurement> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
         }
     }
 
-    pub fn set2(&mut self, result: T2) {
-        self.result2 = Some(result);
+    pub fn total(&self) -> Duration {
+        let duration =
+            |part: &Option<Measurement>| part.as_ref().map_or(Duration::ZERO, |m| m.duration);
+        self.parse + duration(&self.part1) + duration(&self.part2)
     }
 
-    pub fn set2_once(&mut self, result: T2) {
-        if self.result2.is_none() {
-            self.result2 = Some(result);
+    pub fn print(&self) {
+        for part in [1, 2] {
+            if let Some(result) = self.result(part) {
+                let time = format!("({:.3} ms)", millis(result.duration));
+                let mut lines = result.answer.as_deref().unwrap_or("---").lines();
+                let first = lines.next().unwrap_or("");
+                println!("[day {:02}] task {}: {} {}", self.day, part, first, time);
+                for line in lines {
+                    println!("[day {:02}] task {}: {}", self.day, part, line);
+                }
+            }
         }
     }
 }
 
-impl<T1: Display, T2: Display> Drop for Timer<T1, T2> {
-    fn drop(&mut self) {
-        let millis = self.start.elapsed().as_millis();
+/// Collects the timers of all days in a run, to print a summary table at the end.
+#[derive(Default)]
+pub struct Summary {
+    runs: Vec<Run>,
+}
 
-        if let Some(result) = self.result1.take() {
-            println!("[day {:02}] task 1: {} ({} ms)", self.day, result, millis);
-        } else {
-            println!("[day {:02}] task 1: --- ({} ms)", self.day, millis);
-        }
+/// A timed day, along with an identifier of the input it ran on.
+pub struct Run {
+    pub input: String,
+    pub timer: Timer,
+}
+
+impl Summary {
+    pub fn add(&mut self, input: String, timer: Timer) {
+        self.runs.push(Run { input, timer });
+    }
+
+    pub fn runs(&self) -> &[Run] {
+        &self.runs
+    }
+
+    pub fn print(&self) {
+        let part = |timer: &Timer, part| match timer.result(part) {
+            Some(result) => format!("{:.3}", millis(result.duration)),
+            None => String::from("---"),
+        };
 
-        if let Some(result) = self.result2.take() {
-            println!("[day {:02}] task 2: {} ({} ms)", self.day, result, millis);
-        } else {
-            println!("[day {:02}] task 2: --- ({} ms)", self.day, millis);
+        println!();
+        println!(
+            "{:>5} {:>12} {:>12} {:>12} {:>12}",
+            "day", "parse ms", "task 1 ms", "task 2 ms", "total ms"
+        );
+        for Run { timer, .. } in &self.runs {
+            println!(
+                "{:>5} {:>12.3} {:>12} {:>12} {:>12.3}",
+                format!("{:02}", timer.day),
+                millis(timer.parse),
+                part(timer, 1),
+                part(timer, 2),
+                millis(timer.total())
+            );
         }
+        let total = self.runs.iter().map(|run| run.timer.total()).sum();
+        println!("{:>5} {:>51.3}", "total", millis(total));
     }
 }
@@ -1,245 +1,23 @@
-use num::{Num, Signed};
-use std::{
-    fmt::Debug,
-    ops::{Add, AddAssign, Div, Mul, MulAssign, Rem, Sub, SubAssign},
-};
-
-#[derive(PartialEq, Eq, Clone, Copy, Default, Hash)]
-pub struct Vec2<T> {
-    pub x: T,
-    pub y: T,
-}
-
-impl<T> Vec2<T> {
-    pub fn new(x: T, y: T) -> Self {
-        Self { x, y }
-    }
-}
-
-impl<T> From<(T, T)> for Vec2<T> {
-    fn from(v: (T, T)) -> Self {
-        Self::new(v.0, v.1)
-    }
-}
-
-impl<T> Into<(T, T)> for Vec2<T> {
-    fn into(self) -> (T, T) {
-        (self.x, self.y)
-    }
-}
-
-trait VecBoolTestable {
-    fn test(&self) -> bool;
-}
-impl VecBoolTestable for bool {
-    fn test(&self) -> bool {
-        *self
-    }
-}
-
-impl<T: Num + Ord + Copy> Vec2<T> {
-    pub fn min_element(&self) -> T {
-        self.x.min(self.y)
-    }
-    pub fn max_element(&self) -> T {
-        self.x.max(self.y)
-    }
-    pub fn min(&self, rhs: Self) -> Self {
-        let x = self.x.min(rhs.x);
-        let y = self.y.min(rhs.y);
-        Self { x, y }
-    }
-    pub fn max(&self, rhs: Self) -> Self {
-        let x = self.x.max(rhs.x);
-        let y = self.y.max(rhs.y);
-        Self { x, y }
-    }
-}
-
-impl<T: VecBoolTestable> Vec2<T> {
-    pub fn all(&self) -> bool {
-        self.x.test() && self.y.test()
-    }
-    pub fn any(&self) -> bool {
-        self.x.test() || self.y.test()
-    }
-    pub fn none(&self) -> bool {
-        !self.all()
-    }
-}
-
-impl<T: Num + Ord> Vec2<T> {
-    pub fn lt(self, other: Vec2<T>) -> Vec2<bool> {
-        vec2(self.x.lt(&other.x), self.y.lt(&other.y))
-    }
-    pub fn le(self, other: Vec2<T>) -> Vec2<bool> {
-        vec2(self.x.le(&other.x), self.y.le(&other.y))
-    }
-    pub fn gt(self, other: Vec2<T>) -> Vec2<bool> {
-        vec2(self.x.gt(&other.x), self.y.gt(&other.y))
-    }
-    pub fn ge(self, other: Vec2<T>) -> Vec2<bool> {
-        vec2(self.x.ge(&other.x), self.y.ge(&other.y))
-    }
-}
-
-impl<T: Num + Signed> Vec2<T> {
-    pub fn abs(&self) -> Self {
-        let x = self.x.abs();
-        let y = self.y.abs();
-        Self { x, y }
-    }
-    pub fn signum(&self) -> Self {
-        let x = self.x.signum();
-        let y = self.y.signum();
-        Self { x, y }
-    }
-}
-
-impl<T: Num> Add for Vec2<T> {
-    type Output = Self;
-    fn add(self, rhs: Self) -> Self {
-        let x = self.x + rhs.x;
-        let y = self.y + rhs.y;
-        Self { x, y }
-    }
-}
-
-impl<T: Num> Add<(T, T)> for Vec2<T> {
-    type Output = Self;
-    fn add(self, rhs: (T, T)) -> Self {
-        let x = self.x + rhs.0;
-        let y = self.y + rhs.1;
-        Self { x, y }
-    }
-}
-
-impl<T: Num> Sub for Vec2<T> {
-    type Output = Self;
-    fn sub(self, rhs: Self) -> Self {
-        let x = self.x - rhs.x;
-        let y = self.y - rhs.y;
-        Self { x, y }
-    }
-}
-
-impl<T: Num> Sub<(T, T)> for Vec2<T> {
-    type Output = Self;
-    fn sub(self, rhs: (T, T)) -> Self {
-        let x = self.x - rhs.0;
-        let y = self.y - rhs.1;
-        Self { x, y }
-    }
-}
-
-impl<T: Num> Mul for Vec2<T> {
-    type Output = Self;
-    fn mul(self, rhs: Self) -> Self {
-        let x = self.x * rhs.x;
-        let y = self.y * rhs.y;
-        Self { x, y }
-    }
-}
-
-impl<T: Num> Mul<(T, T)> for Vec2<T> {
-    type Output = Self;
-    fn mul(self, rhs: (T, T)) -> Self {
-        let x = self.x * rhs.0;
-        let y = self.y * rhs.1;
-        Self { x, y }
-    }
-}
+#![cfg_attr(not(test), allow(dead_code))]
 
-impl<T: Num + Copy> Mul<T> for Vec2<T> {
-    type Output = Self;
-    fn mul(self, rhs: T) -> Self {
-        let x = self.x * rhs;
-        let y = self.y * rhs;
-        Self { x, y }
-    }
-}
-
-impl<T: Num> Div for Vec2<T> {
-    type Output = Self;
-    fn div(self, rhs: Self) -> Self {
-        let x = self.x / rhs.x;
-        let y = self.y / rhs.y;
-        Self { x, y }
-    }
-}
-
-impl<T: Num + Copy> Div<T> for Vec2<T> {
-    type Output = Self;
-    fn div(self, rhs: T) -> Self {
-        let x = self.x / rhs;
-        let y = self.y / rhs;
-        Self { x, y }
-    }
-}
-
-impl<T: Num> Rem for Vec2<T> {
-    type Output = Self;
-    fn rem(self, rhs: Self) -> Self {
-        let x = self.x % rhs.x;
-        let y = self.y % rhs.y;
-        Self { x, y }
-    }
-}
-
-impl<T: Num + AddAssign> AddAssign for Vec2<T> {
-    fn add_assign(&mut self, rhs: Self) {
-        self.x += rhs.x;
-        self.y += rhs.y;
-    }
-}
-
-impl<T: Num + AddAssign> AddAssign<(T, T)> for Vec2<T> {
-    fn add_assign(&mut self, rhs: (T, T)) {
-        self.x += rhs.0;
-        self.y += rhs.1;
-    }
-}
-
-impl<T: Num + SubAssign> SubAssign for Vec2<T> {
-    fn sub_assign(&mut self, rhs: Self) {
-        self.x -= rhs.x;
-        self.y -= rhs.y;
-    }
-}
-
-impl<T: Num + SubAssign> SubAssign<(T, T)> for Vec2<T> {
-    fn sub_assign(&mut self, rhs: (T, T)) {
-        self.x -= rhs.0;
-        self.y -= rhs.1;
-    }
-}
-
-impl<T: Num + MulAssign> MulAssign for Vec2<T> {
-    fn mul_assign(&mut self, rhs: Self) {
-        self.x *= rhs.x;
-        self.y *= rhs.y;
-    }
-}
+use crate::{
+    vec3::Vec3,
+    vec4::Vec4,
+    vecn::{swizzles, vec_type},
+};
 
-impl<T: Num + Copy + MulAssign> MulAssign<T> for Vec2<T> {
-    fn mul_assign(&mut self, rhs: T) {
-        self.x *= rhs;
-        self.y *= rhs;
-    }
-}
+vec_type!(Vec2, 2, (T, T), x: 0, y: 1);
 
-impl<T: Num + MulAssign> MulAssign<(T, T)> for Vec2<T> {
-    fn mul_assign(&mut self, rhs: (T, T)) {
-        self.x *= rhs.0;
-        self.y *= rhs.1;
-    }
-}
-
-impl<T: Num + Debug> Debug for Vec2<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_tuple("Vec2").field(&self.x).field(&self.y).finish()
-    }
-}
+swizzles!(Vec2;
+    xx: Vec2(x, x); xy: Vec2(x, y);
+    yx: Vec2(y, x); yy: Vec2(y, y);
+    xxx: Vec3(x, x, x); xxy: Vec3(x, x, y); xyx: Vec3(x, y, x); xyy: Vec3(x, y, y);
+    yxx: Vec3(y, x, x); yxy: Vec3(y, x, y); yyx: Vec3(y, y, x); yyy: Vec3(y, y, y);
+    xxxx: Vec4(x, x, x, x); xxxy: Vec4(x, x, x, y); xxyx: Vec4(x, x, y, x); xxyy: Vec4(x, x, y, y);
+    xyxx: Vec4(x, y, x, x); xyxy: Vec4(x, y, x, y); xyyx: Vec4(x, y, y, x); xyyy: Vec4(x, y, y, y);
+    yxxx: Vec4(y, x, x, x); yxxy: Vec4(y, x, x, y); yxyx: Vec4(y, x, y, x); yxyy: Vec4(y, x, y, y);
+    yyxx: Vec4(y, y, x, x); yyxy: Vec4(y, y, x, y); yyyx: Vec4(y, y, y, x); yyyy: Vec4(y, y, y, y);
+);
 
 pub type Vec2i32 = Vec2<i32>;
 pub type Vec2u32 = Vec2<u32>;
@@ -250,3 +28,102 @@ pub type Vec2usize = Vec2<usize>;
 pub fn vec2<T>(x: T, y: T) -> Vec2<T> {
     Vec2::new(x, y)
 }
+
+impl<T: num::Num + Copy> Vec2<T> {
+    /// The z component of the cross product of `self` and `other` extended to three dimensions,
+    /// which is positive if `other` points clockwise of `self` when y points down.
+    pub fn cross(self, other: Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+impl<T: std::ops::Neg<Output = T>> Vec2<T> {
+    /// Rotates by 90° clockwise as seen with y pointing down, the way the grids are printed, so
+    /// east `(1, 0)` turns into south `(0, 1)`.
+    pub fn rot_cw(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// Rotates by 90° counter-clockwise as seen with y pointing down.
+    pub fn rot_ccw(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+}
+
+impl Vec2i32 {
+    /// The offsets to the orthogonal neighbours: -x, +x, -y, +y.
+    pub const NEIGHBORS4: [Self; 4] = [
+        Self { x: -1, y: 0 },
+        Self { x: 1, y: 0 },
+        Self { x: 0, y: -1 },
+        Self { x: 0, y: 1 },
+    ];
+
+    /// The offsets to all neighbours including diagonals, row by row.
+    pub const NEIGHBORS8: [Self; 8] = [
+        Self { x: -1, y: -1 },
+        Self { x: 0, y: -1 },
+        Self { x: 1, y: -1 },
+        Self { x: -1, y: 0 },
+        Self { x: 1, y: 0 },
+        Self { x: -1, y: 1 },
+        Self { x: 0, y: 1 },
+        Self { x: 1, y: 1 },
+    ];
+
+    /// Iterates over the positions on the line from `self` to `to`, both included.
+    pub fn line_to(self, to: Self) -> Line {
+        let diff = to - self;
+        Line {
+            pos: Some(self),
+            to,
+            step: diff.signum(),
+            delta: Self::new(diff.x.abs(), -diff.y.abs()),
+            err: diff.x.abs() - diff.y.abs(),
+        }
+    }
+}
+
+/// The positions on a line between two points, as drawn by Bresenham's algorithm.
+pub struct Line {
+    pos: Option<Vec2i32>,
+    to: Vec2i32,
+    step: Vec2i32,
+    /// The absolute difference along x and the negated one along y.
+    delta: Vec2i32,
+    err: i32,
+}
+
+impl Iterator for Line {
+    type Item = Vec2i32;
+
+    fn next(&mut self) -> Option<Vec2i32> {
+        let ret = self.pos?;
+        if ret == self.to {
+            self.pos = None;
+            return Some(ret);
+        }
+
+        let mut next = ret;
+        let e2 = 2 * self.err;
+        if e2 >= self.delta.y {
+            self.err += self.delta.y;
+            next.x += self.step.x;
+        }
+        if e2 <= self.delta.x {
+            self.err += self.delta.x;
+            next.y += self.step.y;
+        }
+        self.pos = Some(next);
+        Some(ret)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self
+            .pos
+            .map_or(0, |pos| pos.chebyshev(self.to) as usize + 1);
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Line {}
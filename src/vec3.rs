@@ -1,172 +1,48 @@
-use num::{Num, Signed};
-use std::{
-    fmt::Debug,
-    ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign},
-};
-
-#[derive(PartialEq, Clone, Copy, Default, Hash)]
-pub struct Vec3<T> {
-    pub x: T,
-    pub y: T,
-    pub z: T,
-}
-
-impl<T> Vec3<T> {
-    pub fn new(x: T, y: T, z: T) -> Self {
-        Self { x, y, z }
-    }
-}
-
-impl<T: Num + Signed> Vec3<T> {
-    pub fn abs(&self) -> Self {
-        let x = self.x.abs();
-        let y = self.y.abs();
-        let z = self.z.abs();
-        Self { x, y, z }
-    }
-    pub fn signum(&self) -> Self {
-        let x = self.x.signum();
-        let y = self.y.signum();
-        let z = self.z.signum();
-        Self { x, y, z }
-    }
-}
-
-impl<T: Num> Add for Vec3<T> {
-    type Output = Self;
-    fn add(self, rhs: Self) -> Self {
-        let x = self.x + rhs.x;
-        let y = self.y + rhs.y;
-        let z = self.z + rhs.z;
-        Self { x, y, z }
-    }
-}
-
-impl<T: Num> Add<(T, T, T)> for Vec3<T> {
-    type Output = Self;
-    fn add(self, rhs: (T, T, T)) -> Self {
-        let x = self.x + rhs.0;
-        let y = self.y + rhs.1;
-        let z = self.z + rhs.2;
-        Self { x, y, z }
-    }
-}
-
-impl<T: Num> Sub for Vec3<T> {
-    type Output = Self;
-    fn sub(self, rhs: Self) -> Self {
-        let x = self.x - rhs.x;
-        let y = self.y - rhs.y;
-        let z = self.z - rhs.z;
-        Self { x, y, z }
-    }
-}
+#![cfg_attr(not(test), allow(dead_code))]
 
-impl<T: Num> Sub<(T, T, T)> for Vec3<T> {
-    type Output = Self;
-    fn sub(self, rhs: (T, T, T)) -> Self {
-        let x = self.x - rhs.0;
-        let y = self.y - rhs.1;
-        let z = self.z - rhs.2;
-        Self { x, y, z }
-    }
-}
-
-impl<T: Num> Mul for Vec3<T> {
-    type Output = Self;
-    fn mul(self, rhs: Self) -> Self {
-        let x = self.x * rhs.x;
-        let y = self.y * rhs.y;
-        let z = self.z * rhs.z;
-        Self { x, y, z }
-    }
-}
-
-impl<T: Num> Mul<(T, T, T)> for Vec3<T> {
-    type Output = Self;
-    fn mul(self, rhs: (T, T, T)) -> Self {
-        let x = self.x * rhs.0;
-        let y = self.y * rhs.1;
-        let z = self.z * rhs.2;
-        Self { x, y, z }
-    }
-}
-
-impl<T: Num + Copy> Mul<T> for Vec3<T> {
-    type Output = Self;
-    fn mul(self, rhs: T) -> Self {
-        let x = self.x * rhs;
-        let y = self.y * rhs;
-        let z = self.z * rhs;
-        Self { x, y, z }
-    }
-}
-
-impl<T: Num + AddAssign> AddAssign for Vec3<T> {
-    fn add_assign(&mut self, rhs: Self) {
-        self.x += rhs.x;
-        self.y += rhs.y;
-        self.z += rhs.z;
-    }
-}
-
-impl<T: Num + AddAssign> AddAssign<(T, T, T)> for Vec3<T> {
-    fn add_assign(&mut self, rhs: (T, T, T)) {
-        self.x += rhs.0;
-        self.y += rhs.1;
-        self.z += rhs.2;
-    }
-}
-
-impl<T: Num + SubAssign> SubAssign for Vec3<T> {
-    fn sub_assign(&mut self, rhs: Self) {
-        self.x -= rhs.x;
-        self.y -= rhs.y;
-        self.z -= rhs.z;
-    }
-}
-
-impl<T: Num + SubAssign> SubAssign<(T, T, T)> for Vec3<T> {
-    fn sub_assign(&mut self, rhs: (T, T, T)) {
-        self.x -= rhs.0;
-        self.y -= rhs.1;
-        self.z -= rhs.2;
-    }
-}
-
-impl<T: Num + MulAssign> MulAssign for Vec3<T> {
-    fn mul_assign(&mut self, rhs: Self) {
-        self.x *= rhs.x;
-        self.y *= rhs.y;
-        self.z *= rhs.z;
-    }
-}
-
-impl<T: Num + Copy + MulAssign> MulAssign<T> for Vec3<T> {
-    fn mul_assign(&mut self, rhs: T) {
-        self.x *= rhs;
-        self.y *= rhs;
-        self.z *= rhs;
-    }
-}
-
-impl<T: Num + MulAssign> MulAssign<(T, T, T)> for Vec3<T> {
-    fn mul_assign(&mut self, rhs: (T, T, T)) {
-        self.x *= rhs.0;
-        self.y *= rhs.1;
-        self.z *= rhs.2;
-    }
-}
+use crate::{
+    vec2::Vec2,
+    vec4::Vec4,
+    vecn::{swizzles, vec_type},
+};
 
-impl<T: Num + Debug> Debug for Vec3<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_tuple("Vec3")
-            .field(&self.x)
-            .field(&self.y)
-            .field(&self.z)
-            .finish()
-    }
-}
+vec_type!(Vec3, 3, (T, T, T), x: 0, y: 1, z: 2);
+
+swizzles!(Vec3;
+    xx: Vec2(x, x); xy: Vec2(x, y); xz: Vec2(x, z);
+    yx: Vec2(y, x); yy: Vec2(y, y); yz: Vec2(y, z);
+    zx: Vec2(z, x); zy: Vec2(z, y); zz: Vec2(z, z);
+    xxx: Vec3(x, x, x); xxy: Vec3(x, x, y); xxz: Vec3(x, x, z); xyx: Vec3(x, y, x);
+    xyy: Vec3(x, y, y); xyz: Vec3(x, y, z); xzx: Vec3(x, z, x); xzy: Vec3(x, z, y);
+    xzz: Vec3(x, z, z);
+    yxx: Vec3(y, x, x); yxy: Vec3(y, x, y); yxz: Vec3(y, x, z); yyx: Vec3(y, y, x);
+    yyy: Vec3(y, y, y); yyz: Vec3(y, y, z); yzx: Vec3(y, z, x); yzy: Vec3(y, z, y);
+    yzz: Vec3(y, z, z);
+    zxx: Vec3(z, x, x); zxy: Vec3(z, x, y); zxz: Vec3(z, x, z); zyx: Vec3(z, y, x);
+    zyy: Vec3(z, y, y); zyz: Vec3(z, y, z); zzx: Vec3(z, z, x); zzy: Vec3(z, z, y);
+    zzz: Vec3(z, z, z);
+    xxxx: Vec4(x, x, x, x); xxxy: Vec4(x, x, x, y); xxxz: Vec4(x, x, x, z); xxyx: Vec4(x, x, y, x);
+    xxyy: Vec4(x, x, y, y); xxyz: Vec4(x, x, y, z); xxzx: Vec4(x, x, z, x); xxzy: Vec4(x, x, z, y);
+    xxzz: Vec4(x, x, z, z); xyxx: Vec4(x, y, x, x); xyxy: Vec4(x, y, x, y); xyxz: Vec4(x, y, x, z);
+    xyyx: Vec4(x, y, y, x); xyyy: Vec4(x, y, y, y); xyyz: Vec4(x, y, y, z); xyzx: Vec4(x, y, z, x);
+    xyzy: Vec4(x, y, z, y); xyzz: Vec4(x, y, z, z); xzxx: Vec4(x, z, x, x); xzxy: Vec4(x, z, x, y);
+    xzxz: Vec4(x, z, x, z); xzyx: Vec4(x, z, y, x); xzyy: Vec4(x, z, y, y); xzyz: Vec4(x, z, y, z);
+    xzzx: Vec4(x, z, z, x); xzzy: Vec4(x, z, z, y); xzzz: Vec4(x, z, z, z);
+    yxxx: Vec4(y, x, x, x); yxxy: Vec4(y, x, x, y); yxxz: Vec4(y, x, x, z); yxyx: Vec4(y, x, y, x);
+    yxyy: Vec4(y, x, y, y); yxyz: Vec4(y, x, y, z); yxzx: Vec4(y, x, z, x); yxzy: Vec4(y, x, z, y);
+    yxzz: Vec4(y, x, z, z); yyxx: Vec4(y, y, x, x); yyxy: Vec4(y, y, x, y); yyxz: Vec4(y, y, x, z);
+    yyyx: Vec4(y, y, y, x); yyyy: Vec4(y, y, y, y); yyyz: Vec4(y, y, y, z); yyzx: Vec4(y, y, z, x);
+    yyzy: Vec4(y, y, z, y); yyzz: Vec4(y, y, z, z); yzxx: Vec4(y, z, x, x); yzxy: Vec4(y, z, x, y);
+    yzxz: Vec4(y, z, x, z); yzyx: Vec4(y, z, y, x); yzyy: Vec4(y, z, y, y); yzyz: Vec4(y, z, y, z);
+    yzzx: Vec4(y, z, z, x); yzzy: Vec4(y, z, z, y); yzzz: Vec4(y, z, z, z);
+    zxxx: Vec4(z, x, x, x); zxxy: Vec4(z, x, x, y); zxxz: Vec4(z, x, x, z); zxyx: Vec4(z, x, y, x);
+    zxyy: Vec4(z, x, y, y); zxyz: Vec4(z, x, y, z); zxzx: Vec4(z, x, z, x); zxzy: Vec4(z, x, z, y);
+    zxzz: Vec4(z, x, z, z); zyxx: Vec4(z, y, x, x); zyxy: Vec4(z, y, x, y); zyxz: Vec4(z, y, x, z);
+    zyyx: Vec4(z, y, y, x); zyyy: Vec4(z, y, y, y); zyyz: Vec4(z, y, y, z); zyzx: Vec4(z, y, z, x);
+    zyzy: Vec4(z, y, z, y); zyzz: Vec4(z, y, z, z); zzxx: Vec4(z, z, x, x); zzxy: Vec4(z, z, x, y);
+    zzxz: Vec4(z, z, x, z); zzyx: Vec4(z, z, y, x); zzyy: Vec4(z, z, y, y); zzyz: Vec4(z, z, y, z);
+    zzzx: Vec4(z, z, z, x); zzzy: Vec4(z, z, z, y); zzzz: Vec4(z, z, z, z);
+);
 
 pub type Vec3i32 = Vec3<i32>;
 pub type Vec3u32 = Vec3<u32>;
@@ -177,3 +53,25 @@ pub type Vec3usize = Vec3<usize>;
 pub fn vec3<T>(x: T, y: T, z: T) -> Vec3<T> {
     Vec3::new(x, y, z)
 }
+
+impl<T: num::Num + Copy> Vec3<T> {
+    pub fn cross(self, other: Self) -> Self {
+        Self::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+}
+
+impl Vec3i32 {
+    /// The offsets to the neighbours that share a face: -x, +x, -y, +y, -z, +z.
+    pub const NEIGHBORS6: [Self; 6] = [
+        Self { x: -1, y: 0, z: 0 },
+        Self { x: 1, y: 0, z: 0 },
+        Self { x: 0, y: -1, z: 0 },
+        Self { x: 0, y: 1, z: 0 },
+        Self { x: 0, y: 0, z: -1 },
+        Self { x: 0, y: 0, z: 1 },
+    ];
+}
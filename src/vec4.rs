@@ -1,259 +1,99 @@
-use num::{Num, Signed};
-use std::{
-    fmt::Debug,
-    ops::{Add, AddAssign, Div, Mul, MulAssign, Sub, SubAssign},
-};
-
-use crate::{vec2::Vec2, vec3::Vec3};
-
-#[derive(PartialEq, Clone, Copy, Default, Hash)]
-pub struct Vec4<T> {
-    pub x: T,
-    pub y: T,
-    pub z: T,
-    pub w: T,
-}
-
-impl<T: Copy> Vec4<T> {
-    pub fn new(x: T, y: T, z: T, w: T) -> Self {
-        Self { x, y, z, w }
-    }
-
-    pub fn xy(&self) -> Vec2<T> {
-        Vec2::new(self.x, self.y)
-    }
-
-    pub fn xyz(&self) -> Vec3<T> {
-        Vec3::new(self.x, self.y, self.z)
-    }
-
-    pub fn xz(&self) -> Vec2<T> {
-        Vec2::new(self.x, self.z)
-    }
-}
-
-impl<T: Num + Ord + Copy> Vec4<T> {
-    pub fn min_element(&self) -> T {
-        self.x.min(self.y).min(self.z).min(self.w)
-    }
-    pub fn max_element(&self) -> T {
-        self.x.max(self.y).max(self.z).max(self.w)
-    }
-}
-
-impl<T: Num + Ord> Vec4<T> {
-    pub fn lt(self, other: Vec4<T>) -> Vec4<bool> {
-        vec4(
-            self.x.lt(&other.x),
-            self.y.lt(&other.y),
-            self.z.lt(&other.z),
-            self.w.lt(&other.w),
-        )
-    }
-    pub fn le(self, other: Vec4<T>) -> Vec4<bool> {
-        vec4(
-            self.x.le(&other.x),
-            self.y.le(&other.y),
-            self.z.le(&other.z),
-            self.w.le(&other.w),
-        )
-    }
-    pub fn gt(self, other: Vec4<T>) -> Vec4<bool> {
-        vec4(
-            self.x.gt(&other.x),
-            self.y.gt(&other.y),
-            self.z.gt(&other.z),
-            self.w.gt(&other.w),
-        )
-    }
-    pub fn ge(self, other: Vec4<T>) -> Vec4<bool> {
-        vec4(
-            self.x.ge(&other.x),
-            self.y.ge(&other.y),
-            self.z.ge(&other.z),
-            self.w.ge(&other.w),
-        )
-    }
-}
-
-impl<T: Num + Signed> Vec4<T> {
-    pub fn abs(&self) -> Self {
-        let x = self.x.abs();
-        let y = self.y.abs();
-        let z = self.z.abs();
-        let w = self.w.abs();
-        Self { x, y, z, w }
-    }
-    pub fn signum(&self) -> Self {
-        let x = self.x.signum();
-        let y = self.y.signum();
-        let z = self.z.signum();
-        let w = self.w.signum();
-        Self { x, y, z, w }
-    }
-}
-
-impl<T: Num> Add for Vec4<T> {
-    type Output = Self;
-    fn add(self, rhs: Self) -> Self {
-        let x = self.x + rhs.x;
-        let y = self.y + rhs.y;
-        let z = self.z + rhs.z;
-        let w = self.w + rhs.w;
-        Self { x, y, z, w }
-    }
-}
-
-impl<T: Num> Add<(T, T, T, T)> for Vec4<T> {
-    type Output = Self;
-    fn add(self, rhs: (T, T, T, T)) -> Self {
-        let x = self.x + rhs.0;
-        let y = self.y + rhs.1;
-        let z = self.z + rhs.2;
-        let w = self.w + rhs.3;
-        Self { x, y, z, w }
-    }
-}
-
-impl<T: Num> Sub for Vec4<T> {
-    type Output = Self;
-    fn sub(self, rhs: Self) -> Self {
-        let x = self.x - rhs.x;
-        let y = self.y - rhs.y;
-        let z = self.z - rhs.z;
-        let w = self.w - rhs.w;
-        Self { x, y, z, w }
-    }
-}
-
-impl<T: Num> Sub<(T, T, T, T)> for Vec4<T> {
-    type Output = Self;
-    fn sub(self, rhs: (T, T, T, T)) -> Self {
-        let x = self.x - rhs.0;
-        let y = self.y - rhs.1;
-        let z = self.z - rhs.2;
-        let w = self.w - rhs.3;
-        Self { x, y, z, w }
-    }
-}
+#![cfg_attr(not(test), allow(dead_code))]
 
-impl<T: Num> Mul for Vec4<T> {
-    type Output = Self;
-    fn mul(self, rhs: Self) -> Self {
-        let x = self.x * rhs.x;
-        let y = self.y * rhs.y;
-        let z = self.z * rhs.z;
-        let w = self.w * rhs.w;
-        Self { x, y, z, w }
-    }
-}
-
-impl<T: Num> Mul<(T, T, T, T)> for Vec4<T> {
-    type Output = Self;
-    fn mul(self, rhs: (T, T, T, T)) -> Self {
-        let x = self.x * rhs.0;
-        let y = self.y * rhs.1;
-        let z = self.z * rhs.2;
-        let w = self.w * rhs.3;
-        Self { x, y, z, w }
-    }
-}
-
-impl<T: Num + Copy> Mul<T> for Vec4<T> {
-    type Output = Self;
-    fn mul(self, rhs: T) -> Self {
-        let x = self.x * rhs;
-        let y = self.y * rhs;
-        let z = self.z * rhs;
-        let w = self.w * rhs;
-        Self { x, y, z, w }
-    }
-}
-
-impl<T: Num> Div for Vec4<T> {
-    type Output = Self;
-    fn div(self, rhs: Self) -> Self {
-        let x = self.x / rhs.x;
-        let y = self.y / rhs.y;
-        let z = self.z / rhs.z;
-        let w = self.w / rhs.w;
-        Self { x, y, z, w }
-    }
-}
-
-impl<T: Num + AddAssign> AddAssign for Vec4<T> {
-    fn add_assign(&mut self, rhs: Self) {
-        self.x += rhs.x;
-        self.y += rhs.y;
-        self.z += rhs.z;
-        self.w += rhs.w;
-    }
-}
-
-impl<T: Num + AddAssign> AddAssign<(T, T, T, T)> for Vec4<T> {
-    fn add_assign(&mut self, rhs: (T, T, T, T)) {
-        self.x += rhs.0;
-        self.y += rhs.1;
-        self.z += rhs.2;
-        self.w += rhs.3;
-    }
-}
-
-impl<T: Num + SubAssign> SubAssign for Vec4<T> {
-    fn sub_assign(&mut self, rhs: Self) {
-        self.x -= rhs.x;
-        self.y -= rhs.y;
-        self.z -= rhs.z;
-        self.w -= rhs.w;
-    }
-}
-
-impl<T: Num + SubAssign> SubAssign<(T, T, T, T)> for Vec4<T> {
-    fn sub_assign(&mut self, rhs: (T, T, T, T)) {
-        self.x -= rhs.0;
-        self.y -= rhs.1;
-        self.z -= rhs.2;
-        self.w -= rhs.3;
-    }
-}
-
-impl<T: Num + MulAssign> MulAssign for Vec4<T> {
-    fn mul_assign(&mut self, rhs: Self) {
-        self.x *= rhs.x;
-        self.y *= rhs.y;
-        self.z *= rhs.z;
-        self.w *= rhs.w;
-    }
-}
-
-impl<T: Num + Copy + MulAssign> MulAssign<T> for Vec4<T> {
-    fn mul_assign(&mut self, rhs: T) {
-        self.x *= rhs;
-        self.y *= rhs;
-        self.z *= rhs;
-        self.w *= rhs;
-    }
-}
-
-impl<T: Num + MulAssign> MulAssign<(T, T, T, T)> for Vec4<T> {
-    fn mul_assign(&mut self, rhs: (T, T, T, T)) {
-        self.x *= rhs.0;
-        self.y *= rhs.1;
-        self.z *= rhs.2;
-        self.w *= rhs.3;
-    }
-}
+use crate::{
+    vec2::Vec2,
+    vec3::Vec3,
+    vecn::{swizzles, vec_type},
+};
 
-impl<T: Num + Debug> Debug for Vec4<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_tuple("Vec4")
-            .field(&self.x)
-            .field(&self.y)
-            .field(&self.z)
-            .field(&self.w)
-            .finish()
-    }
-}
+vec_type!(Vec4, 4, (T, T, T, T), x: 0, y: 1, z: 2, w: 3);
+
+swizzles!(Vec4;
+    xx: Vec2(x, x); xy: Vec2(x, y); xz: Vec2(x, z); xw: Vec2(x, w);
+    yx: Vec2(y, x); yy: Vec2(y, y); yz: Vec2(y, z); yw: Vec2(y, w);
+    zx: Vec2(z, x); zy: Vec2(z, y); zz: Vec2(z, z); zw: Vec2(z, w);
+    wx: Vec2(w, x); wy: Vec2(w, y); wz: Vec2(w, z); ww: Vec2(w, w);
+    xxx: Vec3(x, x, x); xxy: Vec3(x, x, y); xxz: Vec3(x, x, z); xxw: Vec3(x, x, w);
+    xyx: Vec3(x, y, x); xyy: Vec3(x, y, y); xyz: Vec3(x, y, z); xyw: Vec3(x, y, w);
+    xzx: Vec3(x, z, x); xzy: Vec3(x, z, y); xzz: Vec3(x, z, z); xzw: Vec3(x, z, w);
+    xwx: Vec3(x, w, x); xwy: Vec3(x, w, y); xwz: Vec3(x, w, z); xww: Vec3(x, w, w);
+    yxx: Vec3(y, x, x); yxy: Vec3(y, x, y); yxz: Vec3(y, x, z); yxw: Vec3(y, x, w);
+    yyx: Vec3(y, y, x); yyy: Vec3(y, y, y); yyz: Vec3(y, y, z); yyw: Vec3(y, y, w);
+    yzx: Vec3(y, z, x); yzy: Vec3(y, z, y); yzz: Vec3(y, z, z); yzw: Vec3(y, z, w);
+    ywx: Vec3(y, w, x); ywy: Vec3(y, w, y); ywz: Vec3(y, w, z); yww: Vec3(y, w, w);
+    zxx: Vec3(z, x, x); zxy: Vec3(z, x, y); zxz: Vec3(z, x, z); zxw: Vec3(z, x, w);
+    zyx: Vec3(z, y, x); zyy: Vec3(z, y, y); zyz: Vec3(z, y, z); zyw: Vec3(z, y, w);
+    zzx: Vec3(z, z, x); zzy: Vec3(z, z, y); zzz: Vec3(z, z, z); zzw: Vec3(z, z, w);
+    zwx: Vec3(z, w, x); zwy: Vec3(z, w, y); zwz: Vec3(z, w, z); zww: Vec3(z, w, w);
+    wxx: Vec3(w, x, x); wxy: Vec3(w, x, y); wxz: Vec3(w, x, z); wxw: Vec3(w, x, w);
+    wyx: Vec3(w, y, x); wyy: Vec3(w, y, y); wyz: Vec3(w, y, z); wyw: Vec3(w, y, w);
+    wzx: Vec3(w, z, x); wzy: Vec3(w, z, y); wzz: Vec3(w, z, z); wzw: Vec3(w, z, w);
+    wwx: Vec3(w, w, x); wwy: Vec3(w, w, y); wwz: Vec3(w, w, z); www: Vec3(w, w, w);
+    xxxx: Vec4(x, x, x, x); xxxy: Vec4(x, x, x, y); xxxz: Vec4(x, x, x, z); xxxw: Vec4(x, x, x, w);
+    xxyx: Vec4(x, x, y, x); xxyy: Vec4(x, x, y, y); xxyz: Vec4(x, x, y, z); xxyw: Vec4(x, x, y, w);
+    xxzx: Vec4(x, x, z, x); xxzy: Vec4(x, x, z, y); xxzz: Vec4(x, x, z, z); xxzw: Vec4(x, x, z, w);
+    xxwx: Vec4(x, x, w, x); xxwy: Vec4(x, x, w, y); xxwz: Vec4(x, x, w, z); xxww: Vec4(x, x, w, w);
+    xyxx: Vec4(x, y, x, x); xyxy: Vec4(x, y, x, y); xyxz: Vec4(x, y, x, z); xyxw: Vec4(x, y, x, w);
+    xyyx: Vec4(x, y, y, x); xyyy: Vec4(x, y, y, y); xyyz: Vec4(x, y, y, z); xyyw: Vec4(x, y, y, w);
+    xyzx: Vec4(x, y, z, x); xyzy: Vec4(x, y, z, y); xyzz: Vec4(x, y, z, z); xyzw: Vec4(x, y, z, w);
+    xywx: Vec4(x, y, w, x); xywy: Vec4(x, y, w, y); xywz: Vec4(x, y, w, z); xyww: Vec4(x, y, w, w);
+    xzxx: Vec4(x, z, x, x); xzxy: Vec4(x, z, x, y); xzxz: Vec4(x, z, x, z); xzxw: Vec4(x, z, x, w);
+    xzyx: Vec4(x, z, y, x); xzyy: Vec4(x, z, y, y); xzyz: Vec4(x, z, y, z); xzyw: Vec4(x, z, y, w);
+    xzzx: Vec4(x, z, z, x); xzzy: Vec4(x, z, z, y); xzzz: Vec4(x, z, z, z); xzzw: Vec4(x, z, z, w);
+    xzwx: Vec4(x, z, w, x); xzwy: Vec4(x, z, w, y); xzwz: Vec4(x, z, w, z); xzww: Vec4(x, z, w, w);
+    xwxx: Vec4(x, w, x, x); xwxy: Vec4(x, w, x, y); xwxz: Vec4(x, w, x, z); xwxw: Vec4(x, w, x, w);
+    xwyx: Vec4(x, w, y, x); xwyy: Vec4(x, w, y, y); xwyz: Vec4(x, w, y, z); xwyw: Vec4(x, w, y, w);
+    xwzx: Vec4(x, w, z, x); xwzy: Vec4(x, w, z, y); xwzz: Vec4(x, w, z, z); xwzw: Vec4(x, w, z, w);
+    xwwx: Vec4(x, w, w, x); xwwy: Vec4(x, w, w, y); xwwz: Vec4(x, w, w, z); xwww: Vec4(x, w, w, w);
+    yxxx: Vec4(y, x, x, x); yxxy: Vec4(y, x, x, y); yxxz: Vec4(y, x, x, z); yxxw: Vec4(y, x, x, w);
+    yxyx: Vec4(y, x, y, x); yxyy: Vec4(y, x, y, y); yxyz: Vec4(y, x, y, z); yxyw: Vec4(y, x, y, w);
+    yxzx: Vec4(y, x, z, x); yxzy: Vec4(y, x, z, y); yxzz: Vec4(y, x, z, z); yxzw: Vec4(y, x, z, w);
+    yxwx: Vec4(y, x, w, x); yxwy: Vec4(y, x, w, y); yxwz: Vec4(y, x, w, z); yxww: Vec4(y, x, w, w);
+    yyxx: Vec4(y, y, x, x); yyxy: Vec4(y, y, x, y); yyxz: Vec4(y, y, x, z); yyxw: Vec4(y, y, x, w);
+    yyyx: Vec4(y, y, y, x); yyyy: Vec4(y, y, y, y); yyyz: Vec4(y, y, y, z); yyyw: Vec4(y, y, y, w);
+    yyzx: Vec4(y, y, z, x); yyzy: Vec4(y, y, z, y); yyzz: Vec4(y, y, z, z); yyzw: Vec4(y, y, z, w);
+    yywx: Vec4(y, y, w, x); yywy: Vec4(y, y, w, y); yywz: Vec4(y, y, w, z); yyww: Vec4(y, y, w, w);
+    yzxx: Vec4(y, z, x, x); yzxy: Vec4(y, z, x, y); yzxz: Vec4(y, z, x, z); yzxw: Vec4(y, z, x, w);
+    yzyx: Vec4(y, z, y, x); yzyy: Vec4(y, z, y, y); yzyz: Vec4(y, z, y, z); yzyw: Vec4(y, z, y, w);
+    yzzx: Vec4(y, z, z, x); yzzy: Vec4(y, z, z, y); yzzz: Vec4(y, z, z, z); yzzw: Vec4(y, z, z, w);
+    yzwx: Vec4(y, z, w, x); yzwy: Vec4(y, z, w, y); yzwz: Vec4(y, z, w, z); yzww: Vec4(y, z, w, w);
+    ywxx: Vec4(y, w, x, x); ywxy: Vec4(y, w, x, y); ywxz: Vec4(y, w, x, z); ywxw: Vec4(y, w, x, w);
+    ywyx: Vec4(y, w, y, x); ywyy: Vec4(y, w, y, y); ywyz: Vec4(y, w, y, z); ywyw: Vec4(y, w, y, w);
+    ywzx: Vec4(y, w, z, x); ywzy: Vec4(y, w, z, y); ywzz: Vec4(y, w, z, z); ywzw: Vec4(y, w, z, w);
+    ywwx: Vec4(y, w, w, x); ywwy: Vec4(y, w, w, y); ywwz: Vec4(y, w, w, z); ywww: Vec4(y, w, w, w);
+    zxxx: Vec4(z, x, x, x); zxxy: Vec4(z, x, x, y); zxxz: Vec4(z, x, x, z); zxxw: Vec4(z, x, x, w);
+    zxyx: Vec4(z, x, y, x); zxyy: Vec4(z, x, y, y); zxyz: Vec4(z, x, y, z); zxyw: Vec4(z, x, y, w);
+    zxzx: Vec4(z, x, z, x); zxzy: Vec4(z, x, z, y); zxzz: Vec4(z, x, z, z); zxzw: Vec4(z, x, z, w);
+    zxwx: Vec4(z, x, w, x); zxwy: Vec4(z, x, w, y); zxwz: Vec4(z, x, w, z); zxww: Vec4(z, x, w, w);
+    zyxx: Vec4(z, y, x, x); zyxy: Vec4(z, y, x, y); zyxz: Vec4(z, y, x, z); zyxw: Vec4(z, y, x, w);
+    zyyx: Vec4(z, y, y, x); zyyy: Vec4(z, y, y, y); zyyz: Vec4(z, y, y, z); zyyw: Vec4(z, y, y, w);
+    zyzx: Vec4(z, y, z, x); zyzy: Vec4(z, y, z, y); zyzz: Vec4(z, y, z, z); zyzw: Vec4(z, y, z, w);
+    zywx: Vec4(z, y, w, x); zywy: Vec4(z, y, w, y); zywz: Vec4(z, y, w, z); zyww: Vec4(z, y, w, w);
+    zzxx: Vec4(z, z, x, x); zzxy: Vec4(z, z, x, y); zzxz: Vec4(z, z, x, z); zzxw: Vec4(z, z, x, w);
+    zzyx: Vec4(z, z, y, x); zzyy: Vec4(z, z, y, y); zzyz: Vec4(z, z, y, z); zzyw: Vec4(z, z, y, w);
+    zzzx: Vec4(z, z, z, x); zzzy: Vec4(z, z, z, y); zzzz: Vec4(z, z, z, z); zzzw: Vec4(z, z, z, w);
+    zzwx: Vec4(z, z, w, x); zzwy: Vec4(z, z, w, y); zzwz: Vec4(z, z, w, z); zzww: Vec4(z, z, w, w);
+    zwxx: Vec4(z, w, x, x); zwxy: Vec4(z, w, x, y); zwxz: Vec4(z, w, x, z); zwxw: Vec4(z, w, x, w);
+    zwyx: Vec4(z, w, y, x); zwyy: Vec4(z, w, y, y); zwyz: Vec4(z, w, y, z); zwyw: Vec4(z, w, y, w);
+    zwzx: Vec4(z, w, z, x); zwzy: Vec4(z, w, z, y); zwzz: Vec4(z, w, z, z); zwzw: Vec4(z, w, z, w);
+    zwwx: Vec4(z, w, w, x); zwwy: Vec4(z, w, w, y); zwwz: Vec4(z, w, w, z); zwww: Vec4(z, w, w, w);
+    wxxx: Vec4(w, x, x, x); wxxy: Vec4(w, x, x, y); wxxz: Vec4(w, x, x, z); wxxw: Vec4(w, x, x, w);
+    wxyx: Vec4(w, x, y, x); wxyy: Vec4(w, x, y, y); wxyz: Vec4(w, x, y, z); wxyw: Vec4(w, x, y, w);
+    wxzx: Vec4(w, x, z, x); wxzy: Vec4(w, x, z, y); wxzz: Vec4(w, x, z, z); wxzw: Vec4(w, x, z, w);
+    wxwx: Vec4(w, x, w, x); wxwy: Vec4(w, x, w, y); wxwz: Vec4(w, x, w, z); wxww: Vec4(w, x, w, w);
+    wyxx: Vec4(w, y, x, x); wyxy: Vec4(w, y, x, y); wyxz: Vec4(w, y, x, z); wyxw: Vec4(w, y, x, w);
+    wyyx: Vec4(w, y, y, x); wyyy: Vec4(w, y, y, y); wyyz: Vec4(w, y, y, z); wyyw: Vec4(w, y, y, w);
+    wyzx: Vec4(w, y, z, x); wyzy: Vec4(w, y, z, y); wyzz: Vec4(w, y, z, z); wyzw: Vec4(w, y, z, w);
+    wywx: Vec4(w, y, w, x); wywy: Vec4(w, y, w, y); wywz: Vec4(w, y, w, z); wyww: Vec4(w, y, w, w);
+    wzxx: Vec4(w, z, x, x); wzxy: Vec4(w, z, x, y); wzxz: Vec4(w, z, x, z); wzxw: Vec4(w, z, x, w);
+    wzyx: Vec4(w, z, y, x); wzyy: Vec4(w, z, y, y); wzyz: Vec4(w, z, y, z); wzyw: Vec4(w, z, y, w);
+    wzzx: Vec4(w, z, z, x); wzzy: Vec4(w, z, z, y); wzzz: Vec4(w, z, z, z); wzzw: Vec4(w, z, z, w);
+    wzwx: Vec4(w, z, w, x); wzwy: Vec4(w, z, w, y); wzwz: Vec4(w, z, w, z); wzww: Vec4(w, z, w, w);
+    wwxx: Vec4(w, w, x, x); wwxy: Vec4(w, w, x, y); wwxz: Vec4(w, w, x, z); wwxw: Vec4(w, w, x, w);
+    wwyx: Vec4(w, w, y, x); wwyy: Vec4(w, w, y, y); wwyz: Vec4(w, w, y, z); wwyw: Vec4(w, w, y, w);
+    wwzx: Vec4(w, w, z, x); wwzy: Vec4(w, w, z, y); wwzz: Vec4(w, w, z, z); wwzw: Vec4(w, w, z, w);
+    wwwx: Vec4(w, w, w, x); wwwy: Vec4(w, w, w, y); wwwz: Vec4(w, w, w, z); wwww: Vec4(w, w, w, w);
+);
 
 pub type Vec4i32 = Vec4<i32>;
 pub type Vec4u32 = Vec4<u32>;
@@ -261,6 +101,6 @@ pub type Vec4i64 = Vec4<i64>;
 pub type Vec4u64 = Vec4<u64>;
 pub type Vec4usize = Vec4<usize>;
 
-pub fn vec4<T: Copy>(x: T, y: T, z: T, w: T) -> Vec4<T> {
+pub fn vec4<T>(x: T, y: T, z: T, w: T) -> Vec4<T> {
     Vec4::new(x, y, z, w)
 }
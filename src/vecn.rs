@@ -0,0 +1,333 @@
+//! The shared definition of the small vector types `Vec2`, `Vec3` and `Vec4`.
+
+pub trait VecBoolTestable {
+    fn test(&self) -> bool;
+}
+
+impl VecBoolTestable for bool {
+    fn test(&self) -> bool {
+        *self
+    }
+}
+
+/// Implements a binary operator and its assigning version for a vector with another vector, a
+/// tuple and a scalar on the right hand side.
+macro_rules! vec_op {
+    ($name:ident, ($($tuple:ty),+), $($field:ident: $index:tt),+;
+     $op:ident, $fn:ident, $op_assign:ident, $fn_assign:ident) => {
+        impl<T: num::Num> std::ops::$op for $name<T> {
+            type Output = Self;
+            fn $fn(self, rhs: Self) -> Self {
+                Self { $($field: self.$field.$fn(rhs.$field)),+ }
+            }
+        }
+
+        impl<T: num::Num> std::ops::$op<($($tuple),+)> for $name<T> {
+            type Output = Self;
+            fn $fn(self, rhs: ($($tuple),+)) -> Self {
+                Self { $($field: self.$field.$fn(rhs.$index)),+ }
+            }
+        }
+
+        impl<T: num::Num + Copy> std::ops::$op<T> for $name<T> {
+            type Output = Self;
+            fn $fn(self, rhs: T) -> Self {
+                Self { $($field: self.$field.$fn(rhs)),+ }
+            }
+        }
+
+        impl<T: num::Num + std::ops::$op_assign> std::ops::$op_assign for $name<T> {
+            fn $fn_assign(&mut self, rhs: Self) {
+                $(self.$field.$fn_assign(rhs.$field);)+
+            }
+        }
+
+        impl<T: num::Num + std::ops::$op_assign> std::ops::$op_assign<($($tuple),+)> for $name<T> {
+            fn $fn_assign(&mut self, rhs: ($($tuple),+)) {
+                $(self.$field.$fn_assign(rhs.$index);)+
+            }
+        }
+
+        impl<T: num::Num + Copy + std::ops::$op_assign> std::ops::$op_assign<T> for $name<T> {
+            fn $fn_assign(&mut self, rhs: T) {
+                $(self.$field.$fn_assign(rhs);)+
+            }
+        }
+    };
+}
+
+/// Implements component-wise arithmetic that returns `None` if any component overflows.
+macro_rules! vec_checked {
+    ($name:ident, $($field:ident),+; $trait:ident, $fn:ident) => {
+        impl<T: num::traits::$trait> $name<T> {
+            pub fn $fn(&self, rhs: Self) -> Option<Self> {
+                Some(Self { $($field: self.$field.$fn(&rhs.$field)?),+ })
+            }
+        }
+    };
+}
+
+/// Implements component-wise arithmetic that saturates or wraps around on overflow.
+macro_rules! vec_overflowing {
+    ($name:ident, $($field:ident),+; $trait:ident, $fn:ident) => {
+        impl<T: num::traits::$trait> $name<T> {
+            pub fn $fn(&self, rhs: Self) -> Self {
+                Self { $($field: self.$field.$fn(&rhs.$field)),+ }
+            }
+        }
+    };
+}
+
+/// Implements methods that build a vector out of the given components, such as `v.zyx()`. The
+/// vector types list every combination of two to four of their components, repeats included.
+macro_rules! swizzles {
+    ($name:ident; $($fn:ident: $target:ident($($field:ident),+);)+) => {
+        impl<T: Copy> $name<T> {
+            $(
+                pub fn $fn(&self) -> $target<T> {
+                    $target::new($(self.$field),+)
+                }
+            )+
+        }
+    };
+}
+
+/// Defines a vector type with the given fields, which are also its tuple indices, along with
+/// its arithmetic, comparisons and helpers.
+macro_rules! vec_type {
+    ($name:ident, $len:literal, ($($tuple:ty),+), $($field:ident: $index:tt),+) => {
+        #[derive(PartialEq, Eq, Clone, Copy, Default, Hash)]
+        #[repr(C)]
+        pub struct $name<T> {
+            $(pub $field: T,)+
+        }
+
+        // SAFETY: the struct is `repr(C)` and holds nothing but fields of the same type, so it has
+        // no padding and is valid for any bit pattern that its fields are valid for.
+        unsafe impl<T: bytemuck::Zeroable> bytemuck::Zeroable for $name<T> {}
+        unsafe impl<T: bytemuck::Pod> bytemuck::Pod for $name<T> {}
+
+        impl<T> $name<T> {
+            pub fn new($($field: T),+) -> Self {
+                Self { $($field),+ }
+            }
+
+            /// Applies `f` to every component.
+            pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> $name<U> {
+                $name { $($field: f(self.$field)),+ }
+            }
+
+            /// Combines the components of `self` and `other` pairwise with `f`.
+            pub fn zip_with<U, V>(self, other: $name<U>, mut f: impl FnMut(T, U) -> V) -> $name<V> {
+                $name { $($field: f(self.$field, other.$field)),+ }
+            }
+
+            /// Converts every component to a type that can hold all of its values.
+            pub fn cast<U: From<T>>(self) -> $name<U> {
+                self.map(U::from)
+            }
+
+            /// Converts every component, failing if one of them doesn't fit into `U`.
+            pub fn try_cast<U: TryFrom<T>>(self) -> Result<$name<U>, U::Error> {
+                Ok($name { $($field: U::try_from(self.$field)?),+ })
+            }
+        }
+
+        impl<T> From<($($tuple),+)> for $name<T> {
+            fn from(v: ($($tuple),+)) -> Self {
+                Self { $($field: v.$index),+ }
+            }
+        }
+
+        impl<T> From<$name<T>> for ($($tuple),+) {
+            fn from(v: $name<T>) -> Self {
+                ($(v.$field),+)
+            }
+        }
+
+        impl<T> From<[T; $len]> for $name<T> {
+            fn from(v: [T; $len]) -> Self {
+                let [$($field),+] = v;
+                Self { $($field),+ }
+            }
+        }
+
+        impl<T> From<$name<T>> for [T; $len] {
+            fn from(v: $name<T>) -> Self {
+                [$(v.$field),+]
+            }
+        }
+
+        impl<T> std::ops::Index<usize> for $name<T> {
+            type Output = T;
+            fn index(&self, index: usize) -> &T {
+                match index {
+                    $($index => &self.$field,)+
+                    _ => panic!("index {} out of range for {}", index, stringify!($name)),
+                }
+            }
+        }
+
+        impl<T> std::ops::IndexMut<usize> for $name<T> {
+            fn index_mut(&mut self, index: usize) -> &mut T {
+                match index {
+                    $($index => &mut self.$field,)+
+                    _ => panic!("index {} out of range for {}", index, stringify!($name)),
+                }
+            }
+        }
+
+        impl<T: PartialOrd + Copy> $name<T> {
+            pub fn min_element(&self) -> T {
+                [$(self.$field),+].into_iter().reduce(|a, b| if b < a { b } else { a }).unwrap()
+            }
+            pub fn max_element(&self) -> T {
+                [$(self.$field),+].into_iter().reduce(|a, b| if b > a { b } else { a }).unwrap()
+            }
+            pub fn min(&self, rhs: Self) -> Self {
+                Self { $($field: if rhs.$field < self.$field { rhs.$field } else { self.$field }),+ }
+            }
+            pub fn max(&self, rhs: Self) -> Self {
+                Self { $($field: if rhs.$field > self.$field { rhs.$field } else { self.$field }),+ }
+            }
+        }
+
+        impl<T: num::Num + Copy> $name<T> {
+            /// The sum of all components.
+            pub fn sum(&self) -> T {
+                T::zero() $(+ self.$field)+
+            }
+            /// The product of all components.
+            pub fn product(&self) -> T {
+                T::one() $(* self.$field)+
+            }
+            pub fn dot(self, other: Self) -> T {
+                (self * other).sum()
+            }
+            /// The squared euclidean distance, which stays exact for integers.
+            pub fn distance_squared(self, other: Self) -> T {
+                let diff = self - other;
+                diff.dot(diff)
+            }
+        }
+
+        impl<T: num::Num + num::Signed + PartialOrd + Copy> $name<T> {
+            /// The sum of the distances along each axis.
+            pub fn manhattan(self, other: Self) -> T {
+                (self - other).abs().sum()
+            }
+            /// The largest distance along any axis, which is the number of king moves between
+            /// the two positions.
+            pub fn chebyshev(self, other: Self) -> T {
+                (self - other).abs().max_element()
+            }
+        }
+
+        impl<T: $crate::vecn::VecBoolTestable> $name<T> {
+            pub fn all(&self) -> bool {
+                true $(&& self.$field.test())+
+            }
+            pub fn any(&self) -> bool {
+                false $(|| self.$field.test())+
+            }
+            pub fn none(&self) -> bool {
+                !self.any()
+            }
+        }
+
+        impl<T: PartialOrd> $name<T> {
+            pub fn lt(self, other: $name<T>) -> $name<bool> {
+                $name { $($field: self.$field.lt(&other.$field)),+ }
+            }
+            pub fn le(self, other: $name<T>) -> $name<bool> {
+                $name { $($field: self.$field.le(&other.$field)),+ }
+            }
+            pub fn gt(self, other: $name<T>) -> $name<bool> {
+                $name { $($field: self.$field.gt(&other.$field)),+ }
+            }
+            pub fn ge(self, other: $name<T>) -> $name<bool> {
+                $name { $($field: self.$field.ge(&other.$field)),+ }
+            }
+        }
+
+        impl<T: num::Num + num::Signed> $name<T> {
+            pub fn abs(&self) -> Self {
+                Self { $($field: self.$field.abs()),+ }
+            }
+            pub fn signum(&self) -> Self {
+                Self { $($field: self.$field.signum()),+ }
+            }
+        }
+
+        impl<T: num::Float> $name<T> {
+            pub fn length(self) -> T {
+                self.dot(self).sqrt()
+            }
+            /// Scales the vector to a length of one.
+            pub fn normalize(self) -> Self {
+                self / self.length()
+            }
+            /// Interpolates linearly, giving `self` for `t = 0` and `other` for `t = 1`.
+            pub fn lerp(self, other: Self, t: T) -> Self {
+                self + (other - self) * t
+            }
+            pub fn floor(self) -> Self {
+                self.map(T::floor)
+            }
+            pub fn ceil(self) -> Self {
+                self.map(T::ceil)
+            }
+            pub fn round(self) -> Self {
+                self.map(T::round)
+            }
+        }
+
+        impl<T: num::Num + num::traits::Euclid> $name<T> {
+            /// The component-wise remainder that is never negative, which wraps a position onto
+            /// a torus of size `rhs`.
+            pub fn rem_euclid(&self, rhs: Self) -> Self {
+                Self { $($field: self.$field.rem_euclid(&rhs.$field)),+ }
+            }
+            pub fn div_euclid(&self, rhs: Self) -> Self {
+                Self { $($field: self.$field.div_euclid(&rhs.$field)),+ }
+            }
+        }
+
+        $crate::vecn::vec_checked!($name, $($field),+; CheckedAdd, checked_add);
+        $crate::vecn::vec_checked!($name, $($field),+; CheckedSub, checked_sub);
+        $crate::vecn::vec_checked!($name, $($field),+; CheckedMul, checked_mul);
+        $crate::vecn::vec_overflowing!($name, $($field),+; SaturatingAdd, saturating_add);
+        $crate::vecn::vec_overflowing!($name, $($field),+; SaturatingSub, saturating_sub);
+        $crate::vecn::vec_overflowing!($name, $($field),+; SaturatingMul, saturating_mul);
+        $crate::vecn::vec_overflowing!($name, $($field),+; WrappingAdd, wrapping_add);
+        $crate::vecn::vec_overflowing!($name, $($field),+; WrappingSub, wrapping_sub);
+        $crate::vecn::vec_overflowing!($name, $($field),+; WrappingMul, wrapping_mul);
+
+        impl<T: num::Num + std::ops::Neg<Output = T>> std::ops::Neg for $name<T> {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+
+        $crate::vecn::vec_op!($name, ($($tuple),+), $($field: $index),+; Add, add, AddAssign, add_assign);
+        $crate::vecn::vec_op!($name, ($($tuple),+), $($field: $index),+; Sub, sub, SubAssign, sub_assign);
+        $crate::vecn::vec_op!($name, ($($tuple),+), $($field: $index),+; Mul, mul, MulAssign, mul_assign);
+        $crate::vecn::vec_op!($name, ($($tuple),+), $($field: $index),+; Div, div, DivAssign, div_assign);
+        $crate::vecn::vec_op!($name, ($($tuple),+), $($field: $index),+; Rem, rem, RemAssign, rem_assign);
+
+        impl<T: std::fmt::Debug> std::fmt::Debug for $name<T> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_tuple(stringify!($name))
+                    $(.field(&self.$field))+
+                    .finish()
+            }
+        }
+    };
+}
+
+pub(crate) use swizzles;
+pub(crate) use vec_checked;
+pub(crate) use vec_op;
+pub(crate) use vec_overflowing;
+pub(crate) use vec_type;